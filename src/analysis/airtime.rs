@@ -0,0 +1,93 @@
+use crate::{
+    analysis::{self, FrequencyBand},
+    decode::{min_basic_rate, supported_rates},
+};
+use kawaiifi::{Bss, InformationElement};
+use std::collections::BTreeMap;
+
+// MAC header, FCS and the timestamp, beacon interval and capability fixed fields
+const BEACON_OVERHEAD_BYTES: usize = 24 + 4 + 12;
+const TU_US: f64 = 1024.0;
+// Long DSSS preamble and PLCP header, and the OFDM preamble and SIGNAL field
+const DSSS_PREAMBLE_US: f64 = 192.0;
+const OFDM_PREAMBLE_US: f64 = 20.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelAirtime {
+    pub band: FrequencyBand,
+    pub channel: u8,
+    // Whether a BSS on the channel also operates on a channel that needs radar detection
+    pub dfs: bool,
+    pub bss_count: usize,
+    // The lowest rate beacons on the channel are sent at
+    pub lowest_rate_mbps: f64,
+    pub beacons_per_second: f64,
+    // Percentage of the channel's airtime spent sending beacons
+    pub airtime_percent: f64,
+}
+
+// Estimates how much airtime the beacons of the BSSs on each channel take up
+pub fn beacon_airtime(scan_results: &[Bss]) -> Vec<ChannelAirtime> {
+    let mut channels = BTreeMap::new();
+
+    for bss in scan_results.iter().filter(|bss| bss.beacon_interval() != 0) {
+        let band = analysis::frequency_band(bss);
+        let rate_mbps = beacon_rate_mbps(bss, band);
+        let interval_us = bss.beacon_interval() as f64 * TU_US;
+
+        let channel = channels
+            .entry((band, bss.channel().number()))
+            .or_insert(ChannelAirtime {
+                band,
+                channel: bss.channel().number(),
+                dfs: false,
+                bss_count: 0,
+                lowest_rate_mbps: rate_mbps,
+                beacons_per_second: 0.0,
+                airtime_percent: 0.0,
+            });
+        channel.dfs |= analysis::is_dfs(bss);
+        channel.bss_count += 1;
+        channel.lowest_rate_mbps = channel.lowest_rate_mbps.min(rate_mbps);
+        channel.beacons_per_second += 1_000_000.0 / interval_us;
+        channel.airtime_percent +=
+            100.0 * frame_duration_us(beacon_bytes(bss), rate_mbps) / interval_us;
+    }
+
+    channels.into_values().collect()
+}
+
+fn beacon_bytes(bss: &Bss) -> usize {
+    BEACON_OVERHEAD_BYTES
+        + bss
+            .ies()
+            .iter()
+            .map(|ie| 2 + ie.bytes().len())
+            .sum::<usize>()
+}
+
+// Beacons go out at the lowest basic rate, so fall back to the lowest rate the PHY has
+fn beacon_rate_mbps(bss: &Bss, band: FrequencyBand) -> f64 {
+    let lowest = min_basic_rate(bss.ies()).or_else(|| {
+        supported_rates(bss.ies())
+            .into_iter()
+            .min_by_key(|rate| rate.value)
+    });
+
+    match lowest {
+        Some(rate) => rate.mbps(),
+        None if band == FrequencyBand::TwoPointFourGhz => 1.0,
+        None => 6.0,
+    }
+}
+
+fn frame_duration_us(bytes: usize, rate_mbps: f64) -> f64 {
+    // 1, 2, 5.5 and 11 Mbps are DSSS/CCK, everything else OFDM
+    if rate_mbps <= 2.0 || rate_mbps == 5.5 || rate_mbps == 11.0 {
+        DSSS_PREAMBLE_US + (bytes * 8) as f64 / rate_mbps
+    } else {
+        // SERVICE and tail bits are added before splitting into 4 us symbols
+        let bits_per_symbol = rate_mbps * 4.0;
+        OFDM_PREAMBLE_US + 4.0 * ((16 + bytes * 8 + 6) as f64 / bits_per_symbol).ceil()
+    }
+}
@@ -0,0 +1,212 @@
+use crate::{
+    analysis::CAPABILITY_PRIVACY,
+    decode::{
+        self, rsn,
+        vendor::{self, MICROSOFT_OUI, OWE_TRANSITION_OUI_TYPE, WIFI_ALLIANCE_OUI, WPS_OUI_TYPE},
+        Rsn,
+    },
+};
+use kawaiifi::Bss;
+use std::{cmp::Reverse, fmt::Display};
+
+const WPS_STATE: u16 = 0x1044;
+const WPS_AP_SETUP_LOCKED: u16 = 0x1057;
+const WPS_STATE_NOT_CONFIGURED: u8 = 1;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Severity {
+    Info,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Finding {
+    pub severity: Severity,
+    pub title: String,
+    pub detail: String,
+}
+
+impl Finding {
+    fn new(severity: Severity, title: &str, detail: &str) -> Finding {
+        Finding {
+            severity,
+            title: title.to_string(),
+            detail: detail.to_string(),
+        }
+    }
+}
+
+// Inspects the security related IEs of a BSS, most severe findings first
+pub fn audit(bss: &Bss) -> Vec<Finding> {
+    let ies = bss.ies();
+    let rsn = Rsn::from_ies(ies);
+    let wpa = Rsn::wpa_from_ies(ies);
+    let mut findings = Vec::new();
+
+    if rsn.is_none() && wpa.is_none() {
+        if bss.capability_info() & CAPABILITY_PRIVACY != 0 {
+            findings.push(Finding::new(
+                Severity::Critical,
+                "WEP in use",
+                "Privacy is set without an RSN or WPA element, WEP keys can be recovered in \
+                 minutes",
+            ));
+        } else if decode::find_vendor_ie(ies, WIFI_ALLIANCE_OUI, OWE_TRANSITION_OUI_TYPE).is_some()
+        {
+            findings.push(Finding::new(
+                Severity::Info,
+                "Open network with OWE transition",
+                "Clients that support OWE use the encrypted transition BSS",
+            ));
+        } else {
+            findings.push(Finding::new(
+                Severity::Medium,
+                "Open network without OWE",
+                "Traffic is sent unencrypted, Enhanced Open (OWE) would encrypt it",
+            ));
+        }
+    }
+
+    if wpa.is_some() {
+        findings.push(Finding::new(
+            Severity::Medium,
+            "WPA1 enabled",
+            "The WPA (version 1) element allows clients to connect without RSN",
+        ));
+    }
+
+    for security in rsn.iter().chain(wpa.iter()) {
+        match security.group_cipher_type() {
+            Some(rsn::CIPHER_WEP_40) | Some(rsn::CIPHER_WEP_104) => findings.push(Finding::new(
+                Severity::Critical,
+                "WEP group cipher",
+                "Broadcast and multicast traffic is protected with WEP",
+            )),
+            Some(rsn::CIPHER_TKIP) => findings.push(Finding::new(
+                Severity::High,
+                "TKIP group cipher",
+                "Broadcast and multicast traffic is protected with TKIP",
+            )),
+            _ => (),
+        }
+
+        if security.has_pairwise_cipher(rsn::CIPHER_TKIP) {
+            findings.push(Finding::new(
+                Severity::High,
+                "TKIP pairwise cipher",
+                "TKIP is deprecated and limits clients to 54 Mbps",
+            ));
+        }
+    }
+
+    if let Some(rsn) = &rsn {
+        if rsn.is_sae() && rsn.is_psk() && !rsn.mfp_capable() {
+            findings.push(Finding::new(
+                Severity::High,
+                "PMF disabled on WPA3 transition",
+                "SAE and PSK are both offered but management frame protection is not capable",
+            ));
+        } else if rsn.is_sae() && !rsn.is_psk() && !rsn.mfp_required() {
+            findings.push(Finding::new(
+                Severity::Medium,
+                "PMF not required on WPA3",
+                "WPA3-Personal only networks must require management frame protection",
+            ));
+        } else if !rsn.mfp_capable() {
+            findings.push(Finding::new(
+                Severity::Low,
+                "PMF disabled",
+                "Management frames such as deauthentications can be spoofed",
+            ));
+        }
+    }
+
+    if let Some(wps) = decode::find_vendor_ie(ies, MICROSOFT_OUI, WPS_OUI_TYPE) {
+        let locked = vendor::wps_attribute(wps, WPS_AP_SETUP_LOCKED) == Some(&[1]);
+        if vendor::wps_attribute(wps, WPS_STATE) == Some(&[WPS_STATE_NOT_CONFIGURED]) {
+            findings.push(Finding::new(
+                Severity::High,
+                "WPS unconfigured",
+                "The first registrar to connect can configure the AP",
+            ));
+        } else {
+            findings.push(Finding::new(
+                Severity::Medium,
+                "WPS enabled",
+                if locked {
+                    "The AP setup is locked, but WPS PINs remain exposed to brute force"
+                } else {
+                    "WPS PINs can be brute forced offline or online"
+                },
+            ));
+        }
+    }
+
+    findings.sort_by_key(|finding| Reverse(finding.severity));
+    findings
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Info => write!(f, "Info"),
+            Severity::Low => write!(f, "Low"),
+            Severity::Medium => write!(f, "Medium"),
+            Severity::High => write!(f, "High"),
+            Severity::Critical => write!(f, "Critical"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iw;
+
+    const IW_SCAN: &str = "BSS 3c:84:6a:12:34:56(on wlp3s0)
+\tlast seen: 8766.418s [boottime]
+\tfreq: 5180
+\tbeacon interval: 100 TUs
+\tcapability: ESS Privacy SpectrumMgmt RadioMeasure (0x1111)
+\tsignal: -58.00 dBm
+\tlast seen: 132 ms ago
+\tSSID: Home
+\tSupported rates: 6.0* 9.0 12.0* 18.0 24.0* 36.0 48.0 54.0 
+\tDS Parameter set: channel 36
+\tRSN:\t * Version: 1
+\t\t * Group cipher: CCMP
+\t\t * Pairwise ciphers: CCMP
+\t\t * Authentication suites: PSK SAE
+\t\t * Capabilities: 16-PTKSA-RC 1-GTKSA-RC MFP-capable (0x008c)
+\tWMM:\t * Parameter version 1
+\t\t * BE: CW 15-1023, AIFSN 3
+\t\t * BK: CW 15-1023, AIFSN 7
+\t\t * VI: CW 7-15, AIFSN 2, TXOP 3008 usec
+\t\t * VO: CW 3-7, AIFSN 2, TXOP 1504 usec
+BSS 00:11:22:33:44:55(on wlp3s0)
+\tfreq: 2437
+\tcapability: ESS Privacy ShortPreamble (0x0031)
+\tsignal: -71.00 dBm
+\tSSID: Legacy
+\tDS Parameter set: channel 6
+";
+
+    #[test]
+    fn imported_wpa2_is_not_wep() {
+        let scan = iw::parse_scan(IW_SCAN).unwrap();
+        let findings = audit(&scan[0]);
+        assert!(findings
+            .iter()
+            .all(|finding| finding.severity < Severity::Medium));
+        assert!(!findings.iter().any(|finding| finding.title.contains("WEP")));
+    }
+
+    #[test]
+    fn imported_privacy_without_rsn_is_wep() {
+        let scan = iw::parse_scan(IW_SCAN).unwrap();
+        assert_eq!(audit(&scan[1])[0].title, "WEP in use");
+    }
+}
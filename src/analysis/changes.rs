@@ -0,0 +1,206 @@
+use crate::{
+    analysis::{self, DiffStatus},
+    decode::{self, IeKey},
+};
+use kawaiifi::{Bss, Ie};
+use std::{
+    collections::{HashMap, HashSet},
+    time::Instant,
+};
+
+// IEs whose contents change from beacon to beacon without the AP being reconfigured
+const VOLATILE_IES: &[IeKey] = &[
+    IeKey {
+        id: decode::TIM_ID,
+        ext_id: None,
+        vendor: None,
+    },
+    IeKey {
+        id: decode::BSS_LOAD_ID,
+        ext_id: None,
+        vendor: None,
+    },
+    IeKey {
+        id: decode::ELEMENT_ID_EXTENSION,
+        ext_id: Some(decode::HE_BSS_LOAD_EXT_ID),
+        vendor: None,
+    },
+];
+
+const MAX_LOG_ENTRIES: usize = 1000;
+
+// Passive and DFS scans routinely miss a BSS, so its history is kept for a few scans
+const MAX_MISSED_SCANS: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct IeChange {
+    pub time: Instant,
+    pub bssid: String,
+    pub ssid: String,
+    pub element: String,
+    pub description: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ChangeTracker {
+    previous_ies: HashMap<String, Vec<Ie>>,
+    missed_scans: HashMap<String, usize>,
+    change_counts: HashMap<String, usize>,
+    changed_in_last_scan: HashSet<String>,
+    log: Vec<IeChange>,
+}
+
+impl ChangeTracker {
+    pub fn update(&mut self, scan_results: &[Bss]) {
+        let now = Instant::now();
+        self.changed_in_last_scan.clear();
+
+        for bss in scan_results {
+            let bssid = bss.bssid().to_string();
+            let ies = bss
+                .ies()
+                .iter()
+                .filter(|ie| !VOLATILE_IES.contains(&decode::ie_key(ie)))
+                .cloned()
+                .collect::<Vec<Ie>>();
+
+            if let Some(previous_ies) = self.previous_ies.get(&bssid) {
+                let changes = analysis::diff_ie_lists(previous_ies, &ies)
+                    .into_iter()
+                    .filter(|ie_diff| ie_diff.status != DiffStatus::Same)
+                    .map(|ie_diff| IeChange {
+                        time: now,
+                        bssid: bssid.clone(),
+                        ssid: decode::display_ssid(bss.ies()).unwrap_or_default(),
+                        description: match ie_diff.status {
+                            DiffStatus::LeftOnly => "removed".to_string(),
+                            DiffStatus::RightOnly => "added".to_string(),
+                            _ => ie_diff
+                                .fields
+                                .iter()
+                                .filter(|field| field.is_different())
+                                .map(|field| {
+                                    format!(
+                                        "{} -> {}",
+                                        field.left.as_deref().unwrap_or("(none)"),
+                                        field.right.as_deref().unwrap_or("(none)")
+                                    )
+                                })
+                                .collect::<Vec<String>>()
+                                .join("; "),
+                        },
+                        element: ie_diff.name,
+                    })
+                    .collect::<Vec<IeChange>>();
+
+                if !changes.is_empty() {
+                    *self.change_counts.entry(bssid.clone()).or_default() += changes.len();
+                    self.changed_in_last_scan.insert(bssid.clone());
+                    self.log.extend(changes);
+                }
+            }
+
+            self.previous_ies.insert(bssid, ies);
+        }
+
+        // Forget BSSs that have gone out of range so the history doesn't grow without bound
+        let current = scan_results
+            .iter()
+            .map(|bss| bss.bssid().to_string())
+            .collect::<HashSet<String>>();
+        for bssid in self.previous_ies.keys() {
+            if current.contains(bssid) {
+                self.missed_scans.remove(bssid);
+            } else {
+                *self.missed_scans.entry(bssid.clone()).or_default() += 1;
+            }
+        }
+        let missed_scans = &self.missed_scans;
+        let is_kept = |bssid: &String| {
+            missed_scans.get(bssid).copied().unwrap_or_default() <= MAX_MISSED_SCANS
+        };
+        self.previous_ies.retain(|bssid, _| is_kept(bssid));
+        self.change_counts.retain(|bssid, _| is_kept(bssid));
+        let previous_ies = &self.previous_ies;
+        self.missed_scans
+            .retain(|bssid, _| previous_ies.contains_key(bssid));
+
+        if self.log.len() > MAX_LOG_ENTRIES {
+            self.log.drain(..self.log.len() - MAX_LOG_ENTRIES);
+        }
+    }
+
+    pub fn change_count(&self, bss: &Bss) -> usize {
+        self.change_counts
+            .get(&bss.bssid().to_string())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn changed_in_last_scan(&self, bss: &Bss) -> bool {
+        self.changed_in_last_scan.contains(&bss.bssid().to_string())
+    }
+
+    // Oldest change first
+    pub fn log(&self) -> &[IeChange] {
+        &self.log
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iw;
+
+    const OFFICE: &str = "BSS 00:11:22:33:44:01(on wlp3s0)
+\tfreq: 2412
+\tcapability: ESS (0x0001)
+\tsignal: -50.00 dBm
+\tSSID: Office
+";
+
+    const OFFICE_RENAMED: &str = "BSS 00:11:22:33:44:01(on wlp3s0)
+\tfreq: 2412
+\tcapability: ESS (0x0001)
+\tsignal: -50.00 dBm
+\tSSID: Office2
+";
+
+    const LAB: &str = "BSS 00:11:22:33:44:02(on wlp3s0)
+\tfreq: 2437
+\tcapability: ESS (0x0001)
+\tsignal: -60.00 dBm
+\tSSID: Lab
+";
+
+    fn scan(text: &str) -> Vec<Bss> {
+        iw::parse_scan(text).unwrap()
+    }
+
+    #[test]
+    fn flags_change_across_missed_scan() {
+        let mut tracker = ChangeTracker::default();
+        tracker.update(&scan(&(OFFICE.to_string() + LAB)));
+        tracker.update(&scan(LAB));
+
+        let changed = scan(&(OFFICE_RENAMED.to_string() + LAB));
+        tracker.update(&changed);
+        assert!(tracker.changed_in_last_scan(&changed[0]));
+        assert_eq!(tracker.change_count(&changed[0]), 1);
+        assert_eq!(tracker.change_count(&changed[1]), 0);
+    }
+
+    #[test]
+    fn forgets_bss_after_max_missed_scans() {
+        let mut tracker = ChangeTracker::default();
+        tracker.update(&scan(OFFICE));
+        for _ in 0..=MAX_MISSED_SCANS {
+            tracker.update(&scan(LAB));
+        }
+
+        let changed = scan(OFFICE_RENAMED);
+        tracker.update(&changed);
+        assert!(!tracker.changed_in_last_scan(&changed[0]));
+        assert!(tracker.log().is_empty());
+    }
+}
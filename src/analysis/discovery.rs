@@ -0,0 +1,88 @@
+use crate::{
+    analysis::{self, FrequencyBand},
+    decode::{
+        self,
+        rnr::{self, NeighborAp},
+        SSID_ID,
+    },
+};
+use kawaiifi::Bss;
+use std::collections::BTreeMap;
+
+// A 6 GHz BSS announced in the Reduced Neighbor Report of a 2.4 or 5 GHz BSS
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AnnouncedBss {
+    pub bssid: String,
+    pub channel: u8,
+    pub short_ssid: Option<u32>,
+    // Resolved from the reporting BSS or a scanned SSID with the same short SSID
+    pub ssid: Option<String>,
+    pub announced_by: Vec<String>,
+    pub colocated: bool,
+    // The signal of the BSS if the scan picked it up directly
+    pub observed_signal_dbm: Option<i32>,
+}
+
+pub fn discover_6_ghz(scan_results: &[Bss]) -> Vec<AnnouncedBss> {
+    let mut announced = BTreeMap::new();
+
+    let reporters = scan_results
+        .iter()
+        .filter(|bss| analysis::frequency_band(bss) != FrequencyBand::SixGhz);
+    for reporter in reporters {
+        let neighbors = NeighborAp::from_ies(reporter.ies())
+            .into_iter()
+            .filter(|neighbor| {
+                FrequencyBand::from_operating_class(neighbor.operating_class)
+                    == Some(FrequencyBand::SixGhz)
+            });
+        for neighbor in neighbors {
+            let bssid = match &neighbor.bssid {
+                Some(bssid) => bssid.to_lowercase(),
+                None => continue,
+            };
+
+            let entry = announced
+                .entry(bssid.clone())
+                .or_insert_with(|| AnnouncedBss {
+                    bssid,
+                    channel: neighbor.channel,
+                    short_ssid: neighbor.short_ssid,
+                    ssid: None,
+                    announced_by: Vec::new(),
+                    colocated: false,
+                    observed_signal_dbm: None,
+                });
+            entry
+                .announced_by
+                .push(reporter.bssid().to_string().to_lowercase());
+            entry.colocated |= neighbor.is_colocated();
+            if entry.ssid.is_none() && neighbor.is_same_ssid() {
+                entry.ssid = decode::display_ssid(reporter.ies());
+            }
+        }
+    }
+
+    announced
+        .into_values()
+        .map(|mut bss| {
+            if bss.ssid.is_none() {
+                bss.ssid = bss.short_ssid.and_then(|short_ssid| {
+                    scan_results
+                        .iter()
+                        .find(|observed| {
+                            decode::find_ie(observed.ies(), SSID_ID)
+                                .map(|ssid| rnr::short_ssid(ssid) == short_ssid)
+                                .unwrap_or_default()
+                        })
+                        .and_then(|observed| decode::display_ssid(observed.ies()))
+                });
+            }
+            bss.observed_signal_dbm = scan_results
+                .iter()
+                .find(|observed| observed.bssid().to_string().to_lowercase() == bss.bssid)
+                .map(|observed| i32::from(observed.signal_dbm()));
+            bss
+        })
+        .collect()
+}
@@ -0,0 +1,90 @@
+use crate::{
+    analysis,
+    decode::{regulatory, Country, TpcReport},
+};
+use kawaiifi::Bss;
+use std::fmt::Display;
+
+// Free-space path loss at 1 m is 20 log10(f) - 27.55 dB with f in MHz
+const FSPL_1M_OFFSET_DB: f64 = 27.55;
+// The spread of indoor signal strengths around the model's prediction
+const SHADOWING_DB: f64 = 6.0;
+
+// A log-distance path loss model: free-space loss over the first metre, then the exponent's
+// loss per decade of distance, which is 2 in free space and around 3 to 4 through walls
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PathLossModel {
+    pub exponent: f64,
+    // Used when the AP doesn't advertise its transmit power
+    pub tx_power_dbm: f64,
+}
+
+impl Default for PathLossModel {
+    fn default() -> Self {
+        PathLossModel {
+            exponent: 3.0,
+            tx_power_dbm: 20.0,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DistanceEstimate {
+    pub meters: f64,
+    pub min_meters: f64,
+    pub max_meters: f64,
+}
+
+impl PathLossModel {
+    pub fn estimate(&self, bss: &Bss) -> DistanceEstimate {
+        let (tx_power_dbm, tx_power_uncertainty_db) = self.tx_power(bss);
+        let path_loss_db = tx_power_dbm - bss.signal_dbm() as f64;
+        let uncertainty_db = SHADOWING_DB + tx_power_uncertainty_db;
+
+        let fspl_1m_db =
+            20.0 * (bss.channel().center_freq_mhz() as f64).log10() - FSPL_1M_OFFSET_DB;
+        let meters = |loss_db: f64| {
+            10f64
+                .powf((loss_db - fspl_1m_db) / (10.0 * self.exponent))
+                .max(1.0)
+        };
+
+        DistanceEstimate {
+            meters: meters(path_loss_db),
+            min_meters: meters(path_loss_db - uncertainty_db),
+            max_meters: meters(path_loss_db + uncertainty_db),
+        }
+    }
+
+    // The AP's transmit power and how far off it could be. A TPC Report gives the actual power,
+    // while the Country element only gives the regulatory maximum, which the AP may not use.
+    fn tx_power(&self, bss: &Bss) -> (f64, f64) {
+        if let Some(tpc_report) = TpcReport::from_ies(bss.ies()) {
+            return (tpc_report.tx_power_dbm as f64, 1.0);
+        }
+
+        let max_tx_power_dbm = Country::from_ies(bss.ies()).and_then(|country| {
+            country
+                .subband(bss.channel().number(), analysis::frequency_band(bss))
+                .map(|subband| subband.max_tx_power_dbm)
+        });
+        match max_tx_power_dbm {
+            Some(max_tx_power_dbm) => (
+                max_tx_power_dbm as f64
+                    - regulatory::power_constraint_db(bss.ies()).unwrap_or_default() as f64,
+                4.0,
+            ),
+            None => (self.tx_power_dbm, 6.0),
+        }
+    }
+}
+
+impl Display for DistanceEstimate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:.0} m ({:.0}-{:.0})",
+            self.meters, self.min_meters, self.max_meters
+        )
+    }
+}
@@ -0,0 +1,199 @@
+use crate::decode::{AcParameters, AccessCategory, MuAcParameters};
+
+// The default EDCA parameters an AP advertises to non-AP stations: AIFSN, CWmin, CWmax and
+// TXOP limit in us
+const DEFAULTS: &[(AccessCategory, u8, u16, u16, u32)] = &[
+    (AccessCategory::Background, 7, 15, 1023, 0),
+    (AccessCategory::BestEffort, 3, 15, 1023, 0),
+    (AccessCategory::Video, 2, 7, 15, 3008),
+    (AccessCategory::Voice, 2, 3, 7, 1504),
+];
+
+// The standard has no MU EDCA defaults, these are the values hostapd advertises: AIFSN, CWmin,
+// CWmax and MU EDCA timer in us
+const MU_DEFAULTS: &[(AccessCategory, u8, u16, u16, u32)] = &[
+    (AccessCategory::Background, 15, 511, 1023, 255 * 8 * 1024),
+    (AccessCategory::BestEffort, 8, 511, 1023, 255 * 8 * 1024),
+    (AccessCategory::Video, 5, 31, 127, 255 * 8 * 1024),
+    (AccessCategory::Voice, 5, 31, 127, 255 * 8 * 1024),
+];
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EdcaNote {
+    // Suspicious values are likely to hurt, other notes are only non-default
+    pub suspicious: bool,
+    pub text: String,
+}
+
+impl EdcaNote {
+    fn new(suspicious: bool, text: String) -> EdcaNote {
+        EdcaNote { suspicious, text }
+    }
+}
+
+// The contention parameters an AC Parameter Record and an MU AC Parameter Record share
+struct Contention {
+    access_category: AccessCategory,
+    aifsn: u8,
+    acm: bool,
+    cw_min: u16,
+    cw_max: u16,
+}
+
+impl From<&AcParameters> for Contention {
+    fn from(ac: &AcParameters) -> Contention {
+        Contention {
+            access_category: ac.access_category,
+            aifsn: ac.aifsn,
+            acm: ac.acm,
+            cw_min: ac.cw_min,
+            cw_max: ac.cw_max,
+        }
+    }
+}
+
+impl From<&MuAcParameters> for Contention {
+    fn from(ac: &MuAcParameters) -> Contention {
+        Contention {
+            access_category: ac.access_category,
+            aifsn: ac.aifsn,
+            acm: ac.acm,
+            cw_min: ac.cw_min,
+            cw_max: ac.cw_max,
+        }
+    }
+}
+
+// Compares the parameters of one access category with the defaults and the other categories
+pub fn check_ac(ac: &AcParameters, all: &[AcParameters]) -> Vec<EdcaNote> {
+    let mut notes = Vec::new();
+
+    if ac.aifsn < 2 {
+        notes.push(EdcaNote::new(
+            true,
+            format!("AIFSN {} is below the minimum of 2", ac.aifsn),
+        ));
+    }
+
+    let best_effort = all
+        .iter()
+        .find(|other| other.access_category == AccessCategory::BestEffort)
+        .map(Contention::from);
+    notes.extend(check_contention(&ac.into(), best_effort.as_ref()));
+    notes.extend(default_differences(
+        DEFAULTS,
+        &ac.into(),
+        ("TXOP", ac.txop_limit_us, |us| format!("{} us", us)),
+    ));
+
+    notes
+}
+
+// The MU EDCA counterpart of check_ac, where an AIFSN of 0 disables EDCA access while the MU
+// EDCA timer runs
+pub fn check_mu_ac(ac: &MuAcParameters, all: &[MuAcParameters]) -> Vec<EdcaNote> {
+    let mut notes = Vec::new();
+
+    match ac.aifsn {
+        0 => notes.push(EdcaNote::new(
+            false,
+            "EDCA access is disabled while the MU EDCA timer runs".to_string(),
+        )),
+        1 => notes.push(EdcaNote::new(
+            true,
+            "AIFSN 1 is below the minimum of 2".to_string(),
+        )),
+        _ => (),
+    }
+
+    let best_effort = all
+        .iter()
+        .find(|other| other.access_category == AccessCategory::BestEffort && other.aifsn != 0)
+        .map(Contention::from);
+    notes.extend(check_contention(&ac.into(), best_effort.as_ref()));
+    notes.extend(default_differences(
+        MU_DEFAULTS,
+        &ac.into(),
+        ("MU EDCA Timer", ac.timer_us, |us| {
+            format!("{:.1} ms", us as f64 / 1000.0)
+        }),
+    ));
+
+    notes
+}
+
+fn check_contention(ac: &Contention, best_effort: Option<&Contention>) -> Vec<EdcaNote> {
+    let mut notes = Vec::new();
+
+    if ac.cw_min > ac.cw_max {
+        notes.push(EdcaNote::new(true, "CWmin is above CWmax".to_string()));
+    }
+    if ac.acm
+        && (ac.access_category == AccessCategory::BestEffort
+            || ac.access_category == AccessCategory::Background)
+    {
+        notes.push(EdcaNote::new(
+            true,
+            "Admission control blocks clients that don't send a TSPEC".to_string(),
+        ));
+    }
+
+    // Voice and video must not wait longer than best effort
+    if ac.access_category == AccessCategory::Voice || ac.access_category == AccessCategory::Video {
+        if let Some(best_effort) = best_effort {
+            if ac.aifsn > best_effort.aifsn || ac.cw_min > best_effort.cw_min {
+                notes.push(EdcaNote::new(true, "Lower priority than AC_BE".to_string()));
+            }
+        }
+    }
+
+    notes
+}
+
+// One note per parameter that differs from the defaults for the access category, the last of
+// which is the TXOP limit or MU EDCA timer
+fn default_differences(
+    defaults: &[(AccessCategory, u8, u16, u16, u32)],
+    ac: &Contention,
+    (last_name, last_value, format_last): (&str, u32, fn(u32) -> String),
+) -> Vec<EdcaNote> {
+    let (_, aifsn, cw_min, cw_max, last_default) = match defaults
+        .iter()
+        .find(|(access_category, ..)| *access_category == ac.access_category)
+    {
+        Some(defaults) => defaults,
+        None => return Vec::new(),
+    };
+
+    let differences = [
+        ("AIFSN", ac.aifsn as u32, *aifsn as u32, aifsn.to_string()),
+        (
+            "CWmin",
+            ac.cw_min as u32,
+            *cw_min as u32,
+            cw_min.to_string(),
+        ),
+        (
+            "CWmax",
+            ac.cw_max as u32,
+            *cw_max as u32,
+            cw_max.to_string(),
+        ),
+        (
+            last_name,
+            last_value,
+            *last_default,
+            format_last(*last_default),
+        ),
+    ];
+    differences
+        .iter()
+        .filter(|(_, value, default, _)| value != default)
+        .map(|(name, _, _, default)| {
+            EdcaNote::new(
+                false,
+                format!("{} differs from the default {}", name, default),
+            )
+        })
+        .collect()
+}
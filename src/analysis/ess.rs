@@ -0,0 +1,248 @@
+use crate::{
+    analysis,
+    decode::{
+        self,
+        capabilities::{self, EXTENDED_CAPABILITY_BSS_TRANSITION},
+        rsn, MobilityDomain, Rsn, WmmParameters, SSID_ID,
+    },
+};
+use kawaiifi::Bss;
+use std::collections::{BTreeMap, HashMap};
+
+// An AP whose configuration differs from most of the other APs of its ESS, or an attribute the
+// APs of an ESS disagree on without any value being used by more than half of them
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EssDeviation {
+    // None when there's no majority to deviate from
+    pub bssid: Option<String>,
+    pub ssid: String,
+    pub attribute: String,
+    // The AP's value, or every value with the number of APs using it when there's no majority
+    pub value: String,
+    pub majority: Option<String>,
+    pub majority_count: usize,
+    pub total: usize,
+}
+
+// Compares the roaming related configuration of the APs of every SSID with more than one BSSID
+pub fn check_ess_consistency(scan_results: &[Bss]) -> Vec<EssDeviation> {
+    // Grouped by the raw SSID octets, the escaped SSID is only a label. Hidden BSSs are left
+    // out, as unrelated networks can send zero-filled SSIDs of the same length.
+    let mut ess_members = BTreeMap::new();
+    for bss in scan_results.iter().filter(|bss| !analysis::is_hidden(bss)) {
+        if let Some(ssid) = decode::find_ie(bss.ies(), SSID_ID) {
+            ess_members.entry(ssid).or_insert_with(Vec::new).push(bss)
+        }
+    }
+
+    let mut deviations = Vec::new();
+    for members in ess_members.values().filter(|members| members.len() > 1) {
+        let ssid = decode::display_ssid(members[0].ies()).unwrap_or_default();
+        let profiles = members
+            .iter()
+            .map(|bss| (bss.bssid().to_string(), roaming_profile(bss)))
+            .collect::<Vec<(String, Vec<(String, String)>)>>();
+
+        // Attributes are compared among the APs that report them, e.g. protocols per band
+        let mut values = BTreeMap::new();
+        for (_, profile) in &profiles {
+            for (attribute, value) in profile {
+                *values
+                    .entry(attribute.as_str())
+                    .or_insert_with(HashMap::new)
+                    .entry(value.as_str())
+                    .or_insert(0) += 1;
+            }
+        }
+
+        for (attribute, counts) in &values {
+            let total = counts.values().sum::<usize>();
+            let majority = counts
+                .iter()
+                .find(|(_, count)| **count * 2 > total)
+                .map(|(value, count)| (*value, *count));
+
+            match majority {
+                Some((majority, majority_count)) => {
+                    for (bssid, profile) in &profiles {
+                        for (_, value) in profile
+                            .iter()
+                            .filter(|(name, value)| name == attribute && value != majority)
+                        {
+                            deviations.push(EssDeviation {
+                                bssid: Some(bssid.clone()),
+                                ssid: ssid.clone(),
+                                attribute: attribute.to_string(),
+                                value: value.clone(),
+                                majority: Some(majority.to_string()),
+                                majority_count,
+                                total,
+                            });
+                        }
+                    }
+                }
+                None => {
+                    let mut counts = counts.iter().collect::<Vec<(&&str, &usize)>>();
+                    counts.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+                    deviations.push(EssDeviation {
+                        bssid: None,
+                        ssid: ssid.clone(),
+                        attribute: attribute.to_string(),
+                        value: counts
+                            .iter()
+                            .map(|(value, count)| format!("{} ({})", value, count))
+                            .collect::<Vec<String>>()
+                            .join(", "),
+                        majority: None,
+                        majority_count: 0,
+                        total,
+                    });
+                }
+            }
+        }
+    }
+
+    deviations
+}
+
+fn roaming_profile(bss: &Bss) -> Vec<(String, String)> {
+    let ies = bss.ies();
+    let band = analysis::frequency_band(bss);
+
+    let mut profile = vec![
+        ("Security".to_string(), security_suites(bss)),
+        (
+            "802.11r Mobility Domain".to_string(),
+            match MobilityDomain::from_ies(ies) {
+                Some(mobility_domain) => format!(
+                    "{:04x}{}",
+                    mobility_domain.mdid,
+                    if mobility_domain.ft_over_ds {
+                        " (FT over DS)"
+                    } else {
+                        ""
+                    }
+                ),
+                None => "None".to_string(),
+            },
+        ),
+        (
+            "802.11k".to_string(),
+            if capabilities::neighbor_report(ies) {
+                "Neighbor Report".to_string()
+            } else if capabilities::radio_measurement(ies) {
+                "Without Neighbor Report".to_string()
+            } else {
+                "None".to_string()
+            },
+        ),
+        (
+            "802.11v BSS Transition".to_string(),
+            capabilities::extended_capability(ies, EXTENDED_CAPABILITY_BSS_TRANSITION).to_string(),
+        ),
+        (
+            "WMM".to_string(),
+            match WmmParameters::from_ies(ies) {
+                Some(wmm) => wmm
+                    .ac_parameters
+                    .iter()
+                    .map(|ac| {
+                        format!(
+                            "{} {}/{}/{}/{}",
+                            ac.access_category, ac.aifsn, ac.cw_min, ac.cw_max, ac.txop_limit_us
+                        )
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                None => "None".to_string(),
+            },
+        ),
+    ];
+
+    // APs on different bands legitimately support different protocols
+    profile.push((
+        format!("Protocols ({})", band),
+        bss.wifi_protocols().to_string(),
+    ));
+
+    profile
+}
+
+// The AKMs, pairwise ciphers and PMF setting, which all have to match for fast roaming
+fn security_suites(bss: &Bss) -> String {
+    match Rsn::from_ies(bss.ies()) {
+        Some(rsn) => format!(
+            "{} / {} / PMF {}",
+            rsn.akm_suites
+                .iter()
+                .map(|suite| rsn::akm_suite_name(suite))
+                .collect::<Vec<String>>()
+                .join("+"),
+            rsn.pairwise_ciphers
+                .iter()
+                .map(|suite| rsn::cipher_suite_name(suite))
+                .collect::<Vec<String>>()
+                .join("+"),
+            if rsn.mfp_required() {
+                "required"
+            } else if rsn.mfp_capable() {
+                "capable"
+            } else {
+                "disabled"
+            }
+        ),
+        None => analysis::security(bss),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iw;
+
+    // Two APs of one SSID and two hidden APs of unrelated networks, each pair with a WPA2 and an
+    // open AP
+    const IW_SCAN: &str = "BSS 00:11:22:33:44:01(on wlp3s0)
+\tfreq: 2412
+\tcapability: ESS Privacy (0x0011)
+\tsignal: -50.00 dBm
+\tSSID: Office
+\tRSN:\t * Version: 1
+\t\t * Group cipher: CCMP
+\t\t * Pairwise ciphers: CCMP
+\t\t * Authentication suites: PSK
+\t\t * Capabilities: 1-PTKSA-RC 1-GTKSA-RC (0x0000)
+BSS 00:11:22:33:44:02(on wlp3s0)
+\tfreq: 2437
+\tcapability: ESS (0x0001)
+\tsignal: -60.00 dBm
+\tSSID: Office
+BSS 00:11:22:33:44:03(on wlp3s0)
+\tfreq: 2462
+\tcapability: ESS Privacy (0x0011)
+\tsignal: -55.00 dBm
+\tSSID: \\x00\\x00\\x00\\x00
+\tRSN:\t * Version: 1
+\t\t * Group cipher: CCMP
+\t\t * Pairwise ciphers: CCMP
+\t\t * Authentication suites: PSK
+\t\t * Capabilities: 1-PTKSA-RC 1-GTKSA-RC (0x0000)
+BSS 00:11:22:33:44:04(on wlp3s0)
+\tfreq: 2412
+\tcapability: ESS (0x0001)
+\tsignal: -65.00 dBm
+\tSSID: \\x00\\x00\\x00\\x00
+";
+
+    #[test]
+    fn skips_zero_filled_hidden_ssids() {
+        let scan = iw::parse_scan(IW_SCAN).unwrap();
+        assert!(analysis::is_hidden(&scan[2]) && analysis::is_hidden(&scan[3]));
+
+        let deviations = check_ess_consistency(&scan);
+        assert!(!deviations.is_empty());
+        assert!(deviations
+            .iter()
+            .all(|deviation| deviation.ssid == "Office"));
+    }
+}
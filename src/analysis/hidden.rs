@@ -0,0 +1,43 @@
+use crate::decode::{self, SSID_ID};
+use kawaiifi::Bss;
+use std::collections::HashMap;
+
+// The SSIDs of hidden BSSs, learned from the scans or probe responses that revealed them
+#[derive(Debug, Default)]
+pub struct SsidTracker {
+    learned: HashMap<String, String>,
+}
+
+impl SsidTracker {
+    pub fn update(&mut self, scan_results: &[Bss]) {
+        for bss in scan_results.iter().filter(|bss| !is_hidden(bss)) {
+            if let Some(ssid) = decode::display_ssid(bss.ies()).filter(|ssid| !ssid.is_empty()) {
+                self.learned.insert(bss.bssid().to_string(), ssid);
+            }
+        }
+    }
+
+    // The SSID a hidden BSS was seen with before, if it has been seen with one
+    pub fn learned_ssid(&self, bss: &Bss) -> Option<&str> {
+        if is_hidden(bss) {
+            self.learned
+                .get(&bss.bssid().to_string())
+                .map(String::as_str)
+        } else {
+            None
+        }
+    }
+}
+
+// Hidden BSSs send an empty SSID or one of the real SSID's length that is zero-filled
+pub fn hidden_ssid_len(bss: &Bss) -> Option<usize> {
+    match decode::find_ie(bss.ies(), SSID_ID) {
+        Some(ssid) if ssid.iter().all(|byte| *byte == 0) => Some(ssid.len()),
+        Some(_) => None,
+        None => Some(0),
+    }
+}
+
+pub fn is_hidden(bss: &Bss) -> bool {
+    hidden_ssid_len(bss).is_some()
+}
@@ -0,0 +1,152 @@
+use crate::decode::{self, Field, IeKey};
+use kawaiifi::{Bss, Ie};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DiffStatus {
+    Same,
+    Different,
+    LeftOnly,
+    RightOnly,
+}
+
+#[derive(Debug)]
+pub struct IeDiff {
+    pub name: String,
+    pub status: DiffStatus,
+    pub fields: Vec<FieldDiff>,
+}
+
+#[derive(Debug)]
+pub struct FieldDiff {
+    pub depth: usize,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+impl FieldDiff {
+    pub fn is_different(&self) -> bool {
+        self.left != self.right
+    }
+}
+
+// Aligns the IEs of two BSSs by element ID, extension ID and vendor OUI.
+// Repeated IEs with the same key are paired in the order they were advertised.
+pub fn diff_ies(left: &Bss, right: &Bss) -> Vec<IeDiff> {
+    diff_ie_lists(left.ies(), right.ies())
+}
+
+pub fn diff_ie_lists(left_ies: &[Ie], right_ies: &[Ie]) -> Vec<IeDiff> {
+    let mut aligned = BTreeMap::new();
+    for (ie, key) in keyed(left_ies) {
+        aligned.entry(key).or_insert((None, None)).0 = Some(ie);
+    }
+    for (ie, key) in keyed(right_ies) {
+        aligned.entry(key).or_insert((None, None)).1 = Some(ie);
+    }
+
+    aligned
+        .into_iter()
+        .map(|(_, (left, right))| {
+            let left_fields = left.map(|ie| flatten(ie, left_ies)).unwrap_or_default();
+            let right_fields = right.map(|ie| flatten(ie, right_ies)).unwrap_or_default();
+            let fields = align(&left_fields, &right_fields);
+
+            let status = match (left, right) {
+                (Some(_), None) => DiffStatus::LeftOnly,
+                (None, Some(_)) => DiffStatus::RightOnly,
+                _ if fields.iter().any(FieldDiff::is_different) => DiffStatus::Different,
+                _ => DiffStatus::Same,
+            };
+
+            IeDiff {
+                name: left.or(right).map(decode::ie_name).unwrap_or_default(),
+                status,
+                fields,
+            }
+        })
+        .collect()
+}
+
+fn keyed(ies: &[Ie]) -> Vec<(&Ie, (IeKey, usize))> {
+    let mut occurrences = BTreeMap::new();
+    ies.iter()
+        .map(|ie| {
+            let key = decode::ie_key(ie);
+            let occurrence = occurrences.entry(key.clone()).or_insert(0);
+            *occurrence += 1;
+            (ie, (key, *occurrence))
+        })
+        .collect()
+}
+
+struct FlatField {
+    depth: usize,
+    // The names of the field and its parents, which identify it regardless of position
+    path: Vec<String>,
+    text: String,
+}
+
+fn flatten(ie: &Ie, ies: &[Ie]) -> Vec<FlatField> {
+    fn push(fields: &[Field], parents: &[String], flattened: &mut Vec<FlatField>) {
+        for field in fields {
+            let mut path = parents.to_vec();
+            path.push(field.name.clone());
+            flattened.push(FlatField {
+                depth: parents.len(),
+                path: path.clone(),
+                text: field.to_string(),
+            });
+            push(&field.children, &path, flattened);
+        }
+    }
+
+    let mut flattened = Vec::new();
+    push(&decode::ie_fields(ie, ies), &[], &mut flattened);
+    flattened
+}
+
+// Pairs fields with the same path using their longest common subsequence, so an extra cipher
+// suite or WPS attribute on one side doesn't shift every field after it
+fn align(left: &[FlatField], right: &[FlatField]) -> Vec<FieldDiff> {
+    let mut lengths = vec![vec![0; right.len() + 1]; left.len() + 1];
+    for i in (0..left.len()).rev() {
+        for j in (0..right.len()).rev() {
+            lengths[i][j] = if left[i].path == right[j].path {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut fields = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() || j < right.len() {
+        if i < left.len() && j < right.len() && left[i].path == right[j].path {
+            fields.push(FieldDiff {
+                depth: left[i].depth,
+                left: Some(left[i].text.clone()),
+                right: Some(right[j].text.clone()),
+            });
+            i += 1;
+            j += 1;
+        } else if j == right.len() || (i < left.len() && lengths[i + 1][j] >= lengths[i][j + 1]) {
+            fields.push(FieldDiff {
+                depth: left[i].depth,
+                left: Some(left[i].text.clone()),
+                right: None,
+            });
+            i += 1;
+        } else {
+            fields.push(FieldDiff {
+                depth: right[j].depth,
+                left: None,
+                right: Some(right[j].text.clone()),
+            });
+            j += 1;
+        }
+    }
+
+    fields
+}
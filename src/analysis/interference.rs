@@ -0,0 +1,111 @@
+use crate::{
+    analysis::{channel_width_mhz, is_dfs},
+    decode::display_ssid,
+};
+use kawaiifi::Bss;
+use std::{cmp::Ordering, fmt::Display};
+
+#[derive(Debug, Clone)]
+pub struct Interferer {
+    pub bssid: String,
+    pub ssid: String,
+    pub channel: String,
+    pub signal_dbm: i32,
+    // Fraction of the victim's occupied bandwidth that this BSS overlaps
+    pub overlap: f64,
+    contribution_mw: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Interference {
+    interferers: Vec<Interferer>,
+    total_mw: f64,
+}
+
+impl Interference {
+    pub fn new(bss: &Bss, scan_results: &[Bss]) -> Self {
+        let (low, high) = occupied_mhz(bss);
+
+        let mut interferers = scan_results
+            .iter()
+            .filter(|other| other.bssid() != bss.bssid())
+            .filter_map(|other| {
+                let (other_low, other_high) = occupied_mhz(other);
+                let overlap_mhz = high.min(other_high) - low.max(other_low);
+                if overlap_mhz <= 0.0 {
+                    return None;
+                }
+
+                let overlap = overlap_mhz / (high - low);
+                Some(Interferer {
+                    bssid: other.bssid().to_string(),
+                    ssid: display_ssid(other.ies()).unwrap_or_default(),
+                    channel: if is_dfs(other) {
+                        format!("{} DFS", other.channel().number())
+                    } else {
+                        other.channel().number().to_string()
+                    },
+                    signal_dbm: other.signal_dbm() as i32,
+                    overlap,
+                    contribution_mw: dbm_to_mw(other.signal_dbm() as f64) * overlap,
+                })
+            })
+            .collect::<Vec<Interferer>>();
+
+        // Loudest interferers first
+        interferers.sort_by(|a, b| {
+            b.contribution_mw
+                .partial_cmp(&a.contribution_mw)
+                .unwrap_or(Ordering::Equal)
+        });
+
+        Interference {
+            total_mw: interferers.iter().map(|i| i.contribution_mw).sum(),
+            interferers,
+        }
+    }
+
+    pub fn worst(&self, count: usize) -> &[Interferer] {
+        &self.interferers[..count.min(self.interferers.len())]
+    }
+
+    // The overlap-weighted sum of every interferer's signal, or None if nothing overlaps
+    pub fn total_dbm(&self) -> Option<f64> {
+        if self.interferers.is_empty() {
+            None
+        } else {
+            Some(10.0 * self.total_mw.log10())
+        }
+    }
+}
+
+impl PartialEq for Interference {
+    fn eq(&self, other: &Self) -> bool {
+        self.total_mw == other.total_mw
+    }
+}
+
+impl PartialOrd for Interference {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.total_mw.partial_cmp(&other.total_mw)
+    }
+}
+
+impl Display for Interference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.total_dbm() {
+            Some(dbm) => write!(f, "{:.1} dBm ({})", dbm, self.interferers.len()),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+fn occupied_mhz(bss: &Bss) -> (f64, f64) {
+    let center = bss.channel().center_freq_mhz() as f64;
+    let half_width = channel_width_mhz(bss) as f64 / 2.0;
+    (center - half_width, center + half_width)
+}
+
+fn dbm_to_mw(dbm: f64) -> f64 {
+    10f64.powf(dbm / 10.0)
+}
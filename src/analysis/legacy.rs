@@ -0,0 +1,46 @@
+use crate::{
+    analysis::{self, FrequencyBand},
+    decode::{self, supported_rates},
+};
+use kawaiifi::Bss;
+use std::collections::BTreeMap;
+
+const HT_CAPABILITIES_ID: u8 = 45;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct LegacyBandSummary {
+    pub band: FrequencyBand,
+    pub bss_count: usize,
+    pub legacy_count: usize,
+}
+
+// A BSS that makes every station fall back to 802.11b rates, or that can't do better than 802.11g
+pub fn is_legacy(bss: &Bss) -> bool {
+    let dsss_basic_rates = supported_rates(bss.ies())
+        .iter()
+        .any(|rate| rate.basic && rate.is_dsss());
+    let no_ht = analysis::frequency_band(bss) == FrequencyBand::TwoPointFourGhz
+        && decode::find_ie(bss.ies(), HT_CAPABILITIES_ID).is_none();
+
+    dsss_basic_rates || no_ht
+}
+
+// How many of the BSSs in each band are legacy
+pub fn legacy_summary(scan_results: &[Bss]) -> Vec<LegacyBandSummary> {
+    let mut bands = BTreeMap::new();
+
+    for bss in scan_results {
+        let band = analysis::frequency_band(bss);
+        let summary = bands.entry(band).or_insert(LegacyBandSummary {
+            band,
+            bss_count: 0,
+            legacy_count: 0,
+        });
+        summary.bss_count += 1;
+        if is_legacy(bss) {
+            summary.legacy_count += 1;
+        }
+    }
+
+    bands.into_values().collect()
+}
@@ -0,0 +1,206 @@
+use crate::{
+    analysis::{self, FrequencyBand},
+    decode::{self, supported_rates, Country, ELEMENT_ID_EXTENSION, VENDOR_SPECIFIC_ID},
+};
+use kawaiifi::{Bss, InformationElement};
+
+const DS_PARAMETER_SET_ID: u8 = 3;
+const HT_OPERATION_ID: u8 = 61;
+const VHT_OPERATION_ID: u8 = 192;
+
+// The lengths the standard defines for fixed length elements, or the minimum for variable ones
+const ELEMENT_LENGTHS: &[(u8, &str, usize, usize)] = &[
+    (1, "Supported Rates", 1, 8),
+    (3, "DS Parameter Set", 1, 1),
+    (5, "TIM", 4, 254),
+    (7, "Country", 3, 255),
+    (11, "BSS Load", 5, 5),
+    (32, "Power Constraint", 1, 1),
+    (45, "HT Capabilities", 26, 26),
+    (48, "RSN", 2, 255),
+    (54, "Mobility Domain", 3, 3),
+    (61, "HT Operation", 22, 22),
+    (191, "VHT Capabilities", 12, 12),
+    (192, "VHT Operation", 5, 5),
+];
+
+// The OFDM rates every OFDM PHY must support, in units of 500 kbps
+const MANDATORY_OFDM_RATES: &[u8] = &[12, 24, 48];
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LintProblem {
+    pub element: String,
+    pub description: String,
+}
+
+impl LintProblem {
+    fn new(element: &str, description: String) -> LintProblem {
+        LintProblem {
+            element: element.to_string(),
+            description,
+        }
+    }
+}
+
+// Checks the IEs of a BSS against each other and against the channel it was seen on
+pub fn lint(bss: &Bss) -> Vec<LintProblem> {
+    let mut problems = element_lengths(bss);
+    let ies = bss.ies();
+    let channel = bss.channel().number();
+
+    if let Some(&primary) = decode::find_ie(ies, DS_PARAMETER_SET_ID).and_then(|b| b.first()) {
+        if primary != channel {
+            problems.push(LintProblem::new(
+                "DS Parameter Set",
+                format!(
+                    "Current channel {}, but the BSS is on channel {}",
+                    primary, channel
+                ),
+            ));
+        }
+    }
+
+    if let Some(&primary) = decode::find_ie(ies, HT_OPERATION_ID).and_then(|b| b.first()) {
+        if primary != channel {
+            problems.push(LintProblem::new(
+                "HT Operation",
+                format!(
+                    "Primary channel {}, but the BSS is on channel {}",
+                    primary, channel
+                ),
+            ));
+        }
+    }
+
+    if let Some(bytes) = decode::find_ie(ies, VHT_OPERATION_ID) {
+        if let Some(problem) = vht_operation(bytes, channel, analysis::channel_width_mhz(bss)) {
+            problems.push(LintProblem::new("VHT Operation", problem));
+        }
+    }
+
+    if let Some(country) = Country::from_ies(ies) {
+        if let Some(problem) = country_channel(&country, channel, analysis::frequency_band(bss)) {
+            problems.push(LintProblem::new("Country", problem));
+        }
+    }
+
+    problems.extend(
+        rates(bss)
+            .into_iter()
+            .map(|problem| LintProblem::new("Supported Rates", problem)),
+    );
+
+    problems
+}
+
+fn element_lengths(bss: &Bss) -> Vec<LintProblem> {
+    bss.ies()
+        .iter()
+        .filter(|ie| ie.id() != VENDOR_SPECIFIC_ID && ie.id() != ELEMENT_ID_EXTENSION)
+        .filter_map(|ie| {
+            let length = ie.bytes().len();
+            let (_, name, min, max) = ELEMENT_LENGTHS.iter().find(|(id, ..)| *id == ie.id())?;
+            if length < *min || length > *max {
+                Some(LintProblem::new(
+                    name,
+                    if min == max {
+                        format!("Length {}, expected {}", length, min)
+                    } else {
+                        format!("Length {}, expected {} to {}", length, min, max)
+                    },
+                ))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+// Checks that the primary channel lies within the segment the channel width field describes
+fn vht_operation(bytes: &[u8], primary: u8, width_mhz: u32) -> Option<String> {
+    let (width, segment_0, segment_1) = match bytes {
+        [width, segment_0, segment_1, ..] => (*width, *segment_0, *segment_1),
+        _ => return None,
+    };
+
+    // 20/40 MHz operation is described by the HT Operation element
+    if width == 0 {
+        return None;
+    }
+
+    let (vht_width_mhz, center) = match width {
+        1 if segment_1 == 0 => (80, segment_0),
+        1 if (segment_1 as i16 - segment_0 as i16).abs() == 8 => (160, segment_1),
+        1 => (80, segment_0),
+        2 => (160, segment_0),
+        3 => (80, segment_0),
+        _ => return Some(format!("Reserved channel width {}", width)),
+    };
+
+    // Channel numbers are 5 MHz apart, so the segment spans +/- width / 10 channels less the
+    // half of a 20 MHz channel
+    let half_span = (vht_width_mhz / 10 - 2) as i16;
+    if (primary as i16 - center as i16).abs() > half_span {
+        return Some(format!(
+            "Center segment {} is {} MHz wide but doesn't contain primary channel {}",
+            center, vht_width_mhz, primary
+        ));
+    }
+
+    if width_mhz >= 80 && width_mhz != vht_width_mhz {
+        return Some(format!(
+            "Describes {} MHz, but the BSS reports {} MHz",
+            vht_width_mhz, width_mhz
+        ));
+    }
+
+    None
+}
+
+fn country_channel(country: &Country, channel: u8, band: FrequencyBand) -> Option<String> {
+    // 6 GHz APs usually list operating classes only, leaving nothing to check
+    if country.subbands.is_empty() || band == FrequencyBand::SixGhz {
+        return None;
+    }
+
+    match country.subband(channel, band) {
+        Some(_) => None,
+        None => Some(format!(
+            "{} doesn't list the operating channel {}",
+            country.code, channel
+        )),
+    }
+}
+
+fn rates(bss: &Bss) -> Vec<String> {
+    let rates = supported_rates(bss.ies());
+    if rates.is_empty() {
+        return Vec::new();
+    }
+
+    let mut problems = Vec::new();
+    if !rates.iter().any(|rate| rate.basic) {
+        problems.push("No basic rates".to_string());
+    }
+
+    let has_ofdm_rates = rates.iter().any(|rate| !rate.is_dsss());
+    let missing = MANDATORY_OFDM_RATES
+        .iter()
+        .filter(|value| !rates.iter().any(|rate| rate.value == **value))
+        .map(|value| format!("{}", *value as f64 / 2.0))
+        .collect::<Vec<String>>();
+    if has_ofdm_rates && !missing.is_empty() {
+        problems.push(format!(
+            "Missing mandatory OFDM rates {} Mbps",
+            missing.join(", ")
+        ));
+    }
+
+    if analysis::frequency_band(bss) != FrequencyBand::TwoPointFourGhz
+        && rates.iter().any(|rate| rate.is_dsss())
+    {
+        problems.push("802.11b rates outside of 2.4 GHz".to_string());
+    }
+
+    problems
+}
@@ -0,0 +1,94 @@
+use crate::{
+    analysis::{self, FrequencyBand},
+    decode::{regulatory::is_dfs_channel, rnr::NeighborAp, BasicMultiLink},
+};
+use kawaiifi::Bss;
+use std::collections::BTreeMap;
+
+// An AP MLD and its affiliated APs, one per link
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ApMld {
+    pub mld_mac: String,
+    pub links: Vec<MldLink>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MldLink {
+    pub bssid: String,
+    pub link_id: Option<u8>,
+    pub band: Option<FrequencyBand>,
+    pub channel: u8,
+    pub dfs: bool,
+    // None for links only known from another link's Reduced Neighbor Report
+    pub signal_dbm: Option<i32>,
+}
+
+// Groups the BSSs that are links of the same AP MLD, adding the links their Reduced Neighbor
+// Reports announce but that weren't heard directly
+pub fn group_mlds(scan_results: &[Bss]) -> Vec<ApMld> {
+    let mut mlds = BTreeMap::new();
+    let mut reported = Vec::new();
+
+    for bss in scan_results {
+        let multi_link = match BasicMultiLink::from_ies(bss.ies()) {
+            Some(multi_link) => multi_link,
+            None => continue,
+        };
+
+        mlds.entry(multi_link.mld_mac.clone())
+            .or_insert_with(Vec::new)
+            .push(MldLink {
+                bssid: bss.bssid().to_string().to_lowercase(),
+                link_id: multi_link.link_id,
+                band: Some(analysis::frequency_band(bss)),
+                channel: bss.channel().number(),
+                dfs: analysis::is_dfs(bss),
+                signal_dbm: Some(i32::from(bss.signal_dbm())),
+            });
+
+        // AP MLD ID 0 is the AP MLD of the reporting AP
+        reported.extend(
+            NeighborAp::from_ies(bss.ies())
+                .into_iter()
+                .filter(|neighbor| {
+                    neighbor
+                        .mld_parameters
+                        .map(|parameters| parameters.ap_mld_id)
+                        == Some(0)
+                })
+                .map(|neighbor| (multi_link.mld_mac.clone(), neighbor)),
+        );
+    }
+
+    for (mld_mac, neighbor) in reported {
+        let links = mlds.entry(mld_mac).or_insert_with(Vec::new);
+        let bssid = match &neighbor.bssid {
+            Some(bssid) => bssid.to_lowercase(),
+            None => continue,
+        };
+        let link_id = neighbor.mld_parameters.map(|parameters| parameters.link_id);
+        let band = FrequencyBand::from_operating_class(neighbor.operating_class);
+
+        // Several links may report the same link, which may also have been heard directly
+        if links
+            .iter()
+            .all(|link| link.bssid != bssid && (link.link_id.is_none() || link.link_id != link_id))
+        {
+            links.push(MldLink {
+                bssid,
+                link_id,
+                band,
+                channel: neighbor.channel,
+                dfs: matches!(band, Some(band) if is_dfs_channel(neighbor.channel, band)),
+                signal_dbm: None,
+            });
+        }
+    }
+
+    mlds.into_iter()
+        .map(|(mld_mac, mut links)| {
+            links.sort_by_key(|link| (link.band, link.channel));
+            ApMld { mld_mac, links }
+        })
+        .collect()
+}
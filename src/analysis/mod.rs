@@ -0,0 +1,298 @@
+mod airtime;
+mod audit;
+mod changes;
+mod discovery;
+mod distance;
+mod edca;
+mod ess;
+mod hidden;
+mod ie_diff;
+mod interference;
+mod legacy;
+mod lint;
+mod mld;
+mod rogue;
+mod search;
+
+pub use airtime::{beacon_airtime, ChannelAirtime};
+pub use audit::{audit, Finding, Severity};
+pub use changes::{ChangeTracker, IeChange};
+pub use discovery::{discover_6_ghz, AnnouncedBss};
+pub use distance::{DistanceEstimate, PathLossModel};
+pub use edca::{check_ac, check_mu_ac, EdcaNote};
+pub use ess::{check_ess_consistency, EssDeviation};
+pub use hidden::{hidden_ssid_len, is_hidden, SsidTracker};
+pub use ie_diff::{diff_ie_lists, diff_ies, DiffStatus};
+pub use interference::Interference;
+pub use legacy::{is_legacy, legacy_summary, LegacyBandSummary};
+pub use lint::{lint, LintProblem};
+pub use mld::{group_mlds, ApMld, MldLink};
+pub use rogue::{AlertLog, Allowlist, RogueAlert};
+pub use search::IeSearch;
+
+use crate::decode::{regulatory, rsn, Country, Rsn};
+use kawaiifi::{Bss, ChannelWidth};
+use std::{cmp::Reverse, collections::HashMap, fmt::Display};
+
+const CAPABILITY_PRIVACY: u16 = 0x0010;
+
+#[derive(Debug, Default)]
+pub struct ScanAnalysis {
+    interference: HashMap<String, Interference>,
+    channel_airtime: Vec<ChannelAirtime>,
+    legacy_summary: Vec<LegacyBandSummary>,
+    changes: ChangeTracker,
+    ssids: SsidTracker,
+    findings: HashMap<String, Vec<Finding>>,
+    lint_problems: HashMap<String, Vec<LintProblem>>,
+    ess_deviations: Vec<EssDeviation>,
+    mlds: Vec<ApMld>,
+    announced_6_ghz: Vec<AnnouncedBss>,
+    // The country code most APs advertise
+    majority_country: Option<String>,
+    allowlist: Option<Allowlist>,
+    path_loss_model: PathLossModel,
+    // Why each BSS is thought to be a rogue AP
+    rogues: HashMap<String, Vec<String>>,
+    new_rogue_alerts: Vec<RogueAlert>,
+}
+
+impl ScanAnalysis {
+    // Analyses the latest scan, keeping the history that spans scans
+    pub fn update(&mut self, scan_results: &[Bss]) {
+        self.interference = scan_results
+            .iter()
+            .map(|bss| {
+                (
+                    bss.bssid().to_string(),
+                    Interference::new(bss, scan_results),
+                )
+            })
+            .collect();
+        self.channel_airtime = beacon_airtime(scan_results);
+        self.legacy_summary = legacy_summary(scan_results);
+        self.changes.update(scan_results);
+        self.ssids.update(scan_results);
+        self.findings = scan_results
+            .iter()
+            .map(|bss| (bss.bssid().to_string(), audit(bss)))
+            .collect();
+        self.lint_problems = scan_results
+            .iter()
+            .map(|bss| (bss.bssid().to_string(), lint(bss)))
+            .collect();
+        self.ess_deviations = check_ess_consistency(scan_results);
+        self.mlds = group_mlds(scan_results);
+        self.announced_6_ghz = discover_6_ghz(scan_results);
+
+        let mut country_counts = HashMap::new();
+        for country in scan_results
+            .iter()
+            .filter_map(|bss| Country::from_ies(bss.ies()))
+        {
+            *country_counts.entry(country.code).or_insert(0) += 1;
+        }
+        // A tie leaves no majority rather than picking one of the codes
+        let mut ranked = country_counts.into_iter().collect::<Vec<(String, usize)>>();
+        ranked.sort_unstable_by_key(|(_, count)| Reverse(*count));
+        self.majority_country = match ranked.as_slice() {
+            [(code, _)] => Some(code.clone()),
+            [(code, first), (_, second), ..] if first > second => Some(code.clone()),
+            _ => None,
+        };
+
+        if let Some(allowlist) = &self.allowlist {
+            let (rogues, alerts) = rogue::detect(allowlist, scan_results, &self.rogues);
+            self.rogues = rogues;
+            self.new_rogue_alerts = alerts;
+        }
+    }
+
+    pub fn lint_problems(&self, bss: &Bss) -> &[LintProblem] {
+        self.lint_problems
+            .get(&bss.bssid().to_string())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn ess_deviations(&self) -> &[EssDeviation] {
+        &self.ess_deviations
+    }
+
+    pub fn ess_deviation_count(&self, bss: &Bss) -> usize {
+        let bssid = bss.bssid().to_string();
+        self.ess_deviations
+            .iter()
+            .filter(|deviation| deviation.bssid.as_ref() == Some(&bssid))
+            .count()
+    }
+
+    // The AP MLD the BSS is a link of, if it's an affiliated AP
+    pub fn mld(&self, bss: &Bss) -> Option<&ApMld> {
+        let bssid = bss.bssid().to_string().to_lowercase();
+        self.mlds
+            .iter()
+            .find(|mld| mld.links.iter().any(|link| link.bssid == bssid))
+    }
+
+    pub fn announced_6_ghz(&self) -> &[AnnouncedBss] {
+        &self.announced_6_ghz
+    }
+
+    pub fn majority_country(&self) -> Option<&str> {
+        self.majority_country.as_deref()
+    }
+
+    // Whether the BSS advertises a different country code than most APs
+    pub fn is_country_mismatch(&self, bss: &Bss) -> bool {
+        match (Country::from_ies(bss.ies()), &self.majority_country) {
+            (Some(country), Some(majority)) => country.code != *majority,
+            _ => false,
+        }
+    }
+
+    pub fn set_allowlist(&mut self, allowlist: Allowlist) {
+        self.allowlist = Some(allowlist);
+    }
+
+    pub fn set_path_loss_model(&mut self, path_loss_model: PathLossModel) {
+        self.path_loss_model = path_loss_model;
+    }
+
+    pub fn distance(&self, bss: &Bss) -> DistanceEstimate {
+        self.path_loss_model.estimate(bss)
+    }
+
+    pub fn rogue_reasons(&self, bss: &Bss) -> &[String] {
+        self.rogues
+            .get(&bss.bssid().to_string())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn rogue_count(&self) -> usize {
+        self.rogues.len()
+    }
+
+    // The alerts raised by the latest scan that weren't raised by the scan before it
+    pub fn new_rogue_alerts(&self) -> &[RogueAlert] {
+        &self.new_rogue_alerts
+    }
+
+    pub fn learned_ssid(&self, bss: &Bss) -> Option<&str> {
+        self.ssids.learned_ssid(bss)
+    }
+
+    pub fn changes(&self) -> &ChangeTracker {
+        &self.changes
+    }
+
+    pub fn findings(&self, bss: &Bss) -> &[Finding] {
+        self.findings
+            .get(&bss.bssid().to_string())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn interference(&self, bss: &Bss) -> Option<&Interference> {
+        self.interference.get(&bss.bssid().to_string())
+    }
+
+    pub fn channel_airtime(&self) -> &[ChannelAirtime] {
+        &self.channel_airtime
+    }
+
+    pub fn legacy_summary(&self) -> &[LegacyBandSummary] {
+        &self.legacy_summary
+    }
+}
+
+// A short description of the authentication offered, e.g. "WPA2/WPA3-Personal"
+pub fn security(bss: &Bss) -> String {
+    let rsn = Rsn::from_ies(bss.ies());
+    let wpa = Rsn::wpa_from_ies(bss.ies());
+
+    match (rsn, wpa) {
+        (Some(rsn), _) if rsn.has_akm(rsn::AKM_OWE) => "OWE".to_string(),
+        (Some(rsn), _) if rsn.has_akm(rsn::AKM_8021X_SUITE_B_192) => {
+            "WPA3-Enterprise 192".to_string()
+        }
+        (Some(rsn), wpa) => {
+            let generations = match (
+                wpa.is_some(),
+                rsn.is_sae(),
+                rsn.is_psk() || rsn.is_enterprise(),
+            ) {
+                (_, true, true) => "WPA2/WPA3",
+                (_, true, false) => "WPA3",
+                (true, false, _) => "WPA/WPA2",
+                (false, false, _) => "WPA2",
+            };
+            if rsn.is_enterprise() {
+                format!("{}-Enterprise", generations)
+            } else {
+                format!("{}-Personal", generations)
+            }
+        }
+        (None, Some(wpa)) if wpa.is_enterprise() => "WPA-Enterprise".to_string(),
+        (None, Some(_)) => "WPA-Personal".to_string(),
+        (None, None) if bss.capability_info() & CAPABILITY_PRIVACY != 0 => "WEP".to_string(),
+        (None, None) => "Open".to_string(),
+    }
+}
+
+// Named like the variants of kawaiifi's Band
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum FrequencyBand {
+    TwoPointFourGhz,
+    FiveGhz,
+    SixGhz,
+}
+
+impl FrequencyBand {
+    // The band of a global operating class, as used by e.g. the Reduced Neighbor Report
+    pub fn from_operating_class(operating_class: u8) -> Option<FrequencyBand> {
+        match operating_class {
+            81..=84 => Some(FrequencyBand::TwoPointFourGhz),
+            115..=130 => Some(FrequencyBand::FiveGhz),
+            131..=137 => Some(FrequencyBand::SixGhz),
+            _ => None,
+        }
+    }
+}
+
+impl Display for FrequencyBand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FrequencyBand::TwoPointFourGhz => write!(f, "2.4 GHz"),
+            FrequencyBand::FiveGhz => write!(f, "5 GHz"),
+            FrequencyBand::SixGhz => write!(f, "6 GHz"),
+        }
+    }
+}
+
+pub(crate) fn frequency_band(bss: &Bss) -> FrequencyBand {
+    match bss.channel().center_freq_mhz() {
+        0..=2999 => FrequencyBand::TwoPointFourGhz,
+        3000..=5924 => FrequencyBand::FiveGhz,
+        _ => FrequencyBand::SixGhz,
+    }
+}
+
+pub(crate) fn channel_width_mhz(bss: &Bss) -> u32 {
+    match bss.channel().width() {
+        ChannelWidth::FiveMhz => 5,
+        ChannelWidth::TenMhz => 10,
+        ChannelWidth::TwentyMhzNoHt | ChannelWidth::TwentyMhz => 20,
+        ChannelWidth::FortyMhz => 40,
+        // Only the segment holding the primary channel of an 80+80 MHz BSS is contiguous
+        ChannelWidth::EightyMhz | ChannelWidth::EightyPlusEightyMhz => 80,
+        ChannelWidth::OneSixtyMhz => 160,
+    }
+}
+
+// Whether any channel the BSS operates on needs radar detection
+pub(crate) fn is_dfs(bss: &Bss) -> bool {
+    regulatory::is_dfs_bss(bss.ies(), bss.channel().number(), frequency_band(bss))
+}
@@ -0,0 +1,213 @@
+use crate::{
+    analysis,
+    decode::{display_ssid, vendor},
+};
+use kawaiifi::Bss;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+// The legitimate networks, read from a JSON array such as
+// [{"ssid": "Corp-WiFi", "bssids": ["00:11:22:33:44:55"], "security": "WPA2-Enterprise",
+//   "vendor": "Cisco", "bands": ["5 GHz"]}]
+// Every key but "ssid" is optional
+#[derive(Debug, Clone, Default)]
+pub struct Allowlist {
+    networks: Vec<AllowedNetwork>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct AllowedNetwork {
+    ssid: String,
+    bssids: Vec<String>,
+    security: Option<String>,
+    vendor: Option<String>,
+    bands: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RogueAlert {
+    pub bssid: String,
+    pub ssid: String,
+    pub reason: String,
+}
+
+#[derive(Debug)]
+pub struct AlertLog {
+    file: File,
+}
+
+impl Allowlist {
+    pub fn from_json(text: &str) -> Result<Allowlist, String> {
+        let value: Value = serde_json::from_str(text).map_err(|e| e.to_string())?;
+        let networks = value
+            .as_array()
+            .ok_or_else(|| "The allowlist must be a JSON array".to_string())?
+            .iter()
+            .map(AllowedNetwork::from_json)
+            .collect::<Result<Vec<AllowedNetwork>, String>>()?;
+
+        Ok(Allowlist { networks })
+    }
+
+    // Returns why the BSS doesn't look like any of the allowlisted networks with the same SSID,
+    // if any. An SSID can be listed several times, e.g. once per band or site, and the BSS only
+    // needs to match one of them.
+    pub fn check(&self, bss: &Bss) -> Vec<String> {
+        let ssid = match bss.ssid() {
+            Some(ssid) if !ssid.is_empty() => ssid,
+            _ => return Vec::new(),
+        };
+
+        self.networks
+            .iter()
+            .filter(|network| network.ssid == ssid)
+            .map(|network| network.mismatches(bss))
+            .min_by_key(Vec::len)
+            .unwrap_or_default()
+    }
+}
+
+impl AllowedNetwork {
+    fn from_json(value: &Value) -> Result<AllowedNetwork, String> {
+        let string = |key: &str| value.get(key).and_then(Value::as_str).map(str::to_string);
+        let strings = |key: &str| {
+            value
+                .get(key)
+                .and_then(Value::as_array)
+                .map(|values| {
+                    values
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .map(str::to_string)
+                        .collect::<Vec<String>>()
+                })
+                .unwrap_or_default()
+        };
+
+        Ok(AllowedNetwork {
+            ssid: string("ssid")
+                .ok_or_else(|| "Every allowlisted network needs an \"ssid\"".to_string())?,
+            bssids: strings("bssids")
+                .iter()
+                .map(|bssid| bssid.to_lowercase())
+                .collect(),
+            security: string("security"),
+            vendor: string("vendor"),
+            bands: strings("bands"),
+        })
+    }
+
+    // Why the BSS doesn't look like this network
+    fn mismatches(&self, bss: &Bss) -> Vec<String> {
+        let mut reasons = Vec::new();
+
+        let bssid = bss.bssid().to_string().to_lowercase();
+        if !self.bssids.is_empty() && !self.bssids.contains(&bssid) {
+            reasons.push("Unknown BSSID for an allowlisted SSID".to_string());
+        }
+
+        if let Some(expected) = &self.security {
+            let security = analysis::security(bss);
+            if !security.eq_ignore_ascii_case(expected) {
+                reasons.push(format!("Security {}, expected {}", security, expected));
+            }
+        }
+
+        if let Some(expected) = &self.vendor {
+            let vendors = vendor::vendors(bss.ies());
+            if !vendors
+                .iter()
+                .any(|vendor| vendor.eq_ignore_ascii_case(expected))
+            {
+                reasons.push(format!(
+                    "Vendor {}, expected {}",
+                    if vendors.is_empty() {
+                        "unknown".to_string()
+                    } else {
+                        vendors.join("/")
+                    },
+                    expected
+                ));
+            }
+        }
+
+        let band = bss.channel().band().to_string();
+        if !self.bands.is_empty()
+            && !self
+                .bands
+                .iter()
+                .any(|expected| expected.eq_ignore_ascii_case(&band))
+        {
+            reasons.push(format!(
+                "Band {}, expected {}",
+                band,
+                self.bands.join(" or ")
+            ));
+        }
+
+        reasons
+    }
+}
+
+impl AlertLog {
+    pub fn open(path: &Path) -> io::Result<AlertLog> {
+        Ok(AlertLog {
+            file: OpenOptions::new().create(true).append(true).open(path)?,
+        })
+    }
+
+    pub fn write(&mut self, alerts: &[RogueAlert]) -> io::Result<()> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        for alert in alerts {
+            writeln!(
+                self.file,
+                "{} {} \"{}\": {}",
+                timestamp, alert.bssid, alert.ssid, alert.reason
+            )?;
+        }
+        self.file.flush()
+    }
+}
+
+// Finds the rogue BSSs in a scan, along with the alerts that weren't raised by previous scans
+pub(crate) fn detect(
+    allowlist: &Allowlist,
+    scan_results: &[Bss],
+    previous: &HashMap<String, Vec<String>>,
+) -> (HashMap<String, Vec<String>>, Vec<RogueAlert>) {
+    let mut rogues = HashMap::new();
+    let mut alerts = Vec::new();
+
+    for bss in scan_results {
+        let reasons = allowlist.check(bss);
+        if reasons.is_empty() {
+            continue;
+        }
+
+        let bssid = bss.bssid().to_string();
+        let previous_reasons = previous.get(&bssid).cloned().unwrap_or_default();
+        alerts.extend(
+            reasons
+                .iter()
+                .filter(|reason| !previous_reasons.contains(reason))
+                .map(|reason| RogueAlert {
+                    bssid: bssid.clone(),
+                    ssid: display_ssid(bss.ies()).unwrap_or_default(),
+                    reason: reason.clone(),
+                }),
+        );
+        rogues.insert(bssid, reasons);
+    }
+
+    (rogues, alerts)
+}
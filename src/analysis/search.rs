@@ -0,0 +1,71 @@
+use crate::decode::{self, Field};
+use kawaiifi::{Bss, InformationElement};
+
+// Finds BSSs advertising an IE, either by element ID ("#54"), by a field name and value
+// ("bss transition=true") or by a case-insensitive match against IE names and decoded field
+// text (e.g. "Mobility Domain"). Flags that are unset never match the text, so "twt" finds the
+// APs with a TWT flag set rather than every AP that reports it as false.
+#[derive(Debug, Clone)]
+pub struct IeSearch {
+    query: String,
+    // The field name and value of a "name=value" query
+    field: Option<(String, String)>,
+}
+
+impl IeSearch {
+    pub fn new(query: &str) -> IeSearch {
+        let query = query.trim().to_lowercase();
+        let field = query.find('=').map(|i| {
+            (
+                query[..i].trim().to_string(),
+                query[i + 1..].trim().to_string(),
+            )
+        });
+        IeSearch { query, field }
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn matches(&self, bss: &Bss) -> bool {
+        if let Some(id) = self
+            .query
+            .strip_prefix('#')
+            .and_then(|id| id.parse::<u8>().ok())
+        {
+            return bss.ies().iter().any(|ie| ie.id() == id);
+        }
+
+        bss.ies().iter().any(|ie| {
+            decode::ie_name(ie).to_lowercase().contains(&self.query)
+                || self.matches_fields(&decode::ie_fields(ie, bss.ies()))
+        })
+    }
+
+    fn matches_fields(&self, fields: &[Field]) -> bool {
+        fields.iter().any(|field| {
+            let (name, value) = name_and_value(field);
+            let matches = match &self.field {
+                Some((query_name, query_value)) => {
+                    name.contains(query_name.as_str()) && value == *query_value
+                }
+                None => value != "false" && field.to_string().to_lowercase().contains(&self.query),
+            };
+            matches || self.matches_fields(&field.children)
+        })
+    }
+}
+
+// The lowercased name and value of a field, splitting the text of fields kawaiifi formatted
+fn name_and_value(field: &Field) -> (String, String) {
+    if field.value.is_empty() {
+        if let Some(i) = field.name.rfind(": ") {
+            return (
+                field.name[..i].trim().to_lowercase(),
+                field.name[i + 2..].trim().to_lowercase(),
+            );
+        }
+    }
+    (field.name.to_lowercase(), field.value.trim().to_lowercase())
+}
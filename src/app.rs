@@ -1,11 +1,18 @@
-use crate::widgets::{
-    BssTable, BssTableColumnHeader, BssTableState, IeTable, IeTableState, TableSortOrder,
+use crate::{
+    analysis::{AlertLog, Allowlist, IeSearch, PathLossModel},
+    widgets::{
+        self, BssTable, BssTableColumnHeader, BssTableState, ChangeLogPopup, ChannelSummaryPopup,
+        DiscoveryPopup, EdcaPopup, EssPopup, FindingsPopup, IeDiffState, IeDiffView, IeTable,
+        IeTableState, InterferencePopup, LintPopup, LocateState, LocateView, RegulatoryPopup,
+        TableSortOrder,
+    },
 };
 use kawaiifi::Bss;
 use std::io::{self, Stdout};
 use tui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Rect},
+    widgets::{Block, Borders, Clear, Paragraph, TableState},
     Terminal,
 };
 
@@ -14,6 +21,21 @@ pub struct App {
     bss_table: BssTableState,
     ie_table: IeTableState,
     layout: Layout,
+    show_interference_popup: bool,
+    show_change_log: bool,
+    show_findings: bool,
+    show_lint: bool,
+    show_ess: bool,
+    // The selected announced 6 GHz BSS while the discovery popup is open
+    discovery: Option<TableState>,
+    show_regulatory: bool,
+    show_edca: bool,
+    show_channel_summary: bool,
+    ie_diff: Option<IeDiffState>,
+    locate: Option<LocateState>,
+    // The IE search being typed, if any
+    search_input: Option<String>,
+    alert_log: Option<AlertLog>,
 }
 
 impl App {
@@ -21,9 +43,39 @@ impl App {
         App::default()
     }
 
-    pub fn update_scan_results(&mut self, scan_results: Vec<Bss>) {
+    pub fn set_allowlist(&mut self, allowlist: Allowlist, alert_log: AlertLog) {
+        self.bss_table.set_allowlist(allowlist);
+        self.alert_log = Some(alert_log);
+    }
+
+    pub fn set_path_loss_model(&mut self, path_loss_model: PathLossModel) {
+        self.bss_table.set_path_loss_model(path_loss_model);
+    }
+
+    pub fn update_scan_results(&mut self, scan_results: Vec<Bss>) -> io::Result<()> {
         self.bss_table.set_scan_results(scan_results);
         self.reset_ie_table();
+
+        if let Some(alert_log) = self.alert_log.as_mut() {
+            alert_log.write(self.bss_table.analysis().new_rogue_alerts())?;
+        }
+
+        if let Some(locate) = self.locate.as_mut() {
+            locate.update(self.bss_table.bss(locate.bssid()));
+        }
+
+        // Keep comparing the same two BSSs for as long as they're still being seen
+        if let Some(ie_diff) = self.ie_diff.as_mut() {
+            let (left_bssid, right_bssid) = ie_diff.bssids();
+            if let (Some(left), Some(right)) = (
+                self.bss_table.bss(left_bssid),
+                self.bss_table.bss(right_bssid),
+            ) {
+                ie_diff.set_bss(left, right);
+            }
+        }
+
+        Ok(())
     }
 
     pub fn sort_bss_table(
@@ -37,43 +89,366 @@ impl App {
 
     pub fn reset_ie_table(&mut self) {
         if let Some(selected_bss) = self.bss_table.selected_bss() {
-            self.ie_table.set_ies(selected_bss.ies());
+            self.ie_table
+                .set_ies(Some(selected_bss.bssid().to_string()), selected_bss.ies());
+        } else {
+            self.ie_table.set_ies(None, &[]);
         }
     }
 
     pub fn select_next(&mut self) {
+        if let Some(ie_diff) = self.ie_diff.as_mut() {
+            ie_diff.select_next();
+            return;
+        }
+
+        if let Some(discovery) = self.discovery.as_mut() {
+            let announced = self.bss_table.analysis().announced_6_ghz().len();
+            if let Some(selected) = discovery.selected() {
+                if announced > selected + 1 {
+                    discovery.select(Some(selected + 1));
+                }
+            }
+            return;
+        }
+
         if self.bss_table.is_focused() {
             self.bss_table.select_next();
-        } else {
+        } else if self.ie_table.is_focused() {
             self.ie_table.select_next();
+        } else {
+            self.ie_table.select_next_field();
         }
         self.reset_ie_table();
     }
 
     pub fn select_previous(&mut self) {
+        if let Some(ie_diff) = self.ie_diff.as_mut() {
+            ie_diff.select_previous();
+            return;
+        }
+
+        if let Some(discovery) = self.discovery.as_mut() {
+            if let Some(selected) = discovery.selected() {
+                if selected > 0 {
+                    discovery.select(Some(selected - 1));
+                }
+            }
+            return;
+        }
+
         if self.bss_table.is_focused() {
             self.bss_table.select_previous();
-        } else {
+        } else if self.ie_table.is_focused() {
             self.ie_table.select_previous();
+        } else {
+            self.ie_table.select_previous_field();
         }
         self.reset_ie_table();
     }
 
+    pub fn toggle_selected_field(&mut self) {
+        if self.ie_table.is_field_list_focused() {
+            self.ie_table.toggle_selected_field();
+        }
+    }
+
     pub fn focus_next(&mut self) {
         if self.bss_table.is_focused() {
             self.ie_table.focus();
             self.bss_table.unfocus();
         } else if self.ie_table.is_focused() {
+            self.ie_table.focus_field_list();
+            self.ie_table.unfocus();
+        } else if self.ie_table.is_field_list_focused() {
+            self.bss_table.focus();
+            self.ie_table.unfocus_field_list();
+        }
+    }
+
+    pub fn toggle_interference_popup(&mut self) {
+        self.show_interference_popup = !self.show_interference_popup;
+    }
+
+    pub fn is_searching(&self) -> bool {
+        self.search_input.is_some()
+    }
+
+    pub fn start_search(&mut self) {
+        self.search_input = Some(String::new());
+    }
+
+    pub fn push_search_char(&mut self, c: char) {
+        if let Some(search_input) = self.search_input.as_mut() {
+            search_input.push(c);
+        }
+    }
+
+    pub fn pop_search_char(&mut self) {
+        if let Some(search_input) = self.search_input.as_mut() {
+            search_input.pop();
+        }
+    }
+
+    // Filters the BSS table down to the BSSs matching the typed search, or clears the
+    // search if nothing was typed
+    pub fn apply_search(&mut self) {
+        if let Some(search_input) = self.search_input.take() {
+            if search_input.trim().is_empty() {
+                self.bss_table.set_search(None);
+            } else {
+                self.bss_table
+                    .set_search(Some(IeSearch::new(&search_input)));
+            }
+            self.reset_ie_table();
+        }
+    }
+
+    pub fn cancel_search(&mut self) {
+        self.search_input = None;
+    }
+
+    pub fn toggle_change_log(&mut self) {
+        self.show_change_log = !self.show_change_log;
+    }
+
+    pub fn toggle_findings(&mut self) {
+        self.show_findings = !self.show_findings;
+    }
+
+    pub fn toggle_lint(&mut self) {
+        self.show_lint = !self.show_lint;
+    }
+
+    pub fn toggle_feature_columns(&mut self) {
+        self.bss_table.toggle_feature_columns();
+    }
+
+    pub fn toggle_hidden_only(&mut self) {
+        self.bss_table.toggle_hidden_only();
+        self.reset_ie_table();
+    }
+
+    pub fn toggle_mld_grouping(&mut self) {
+        self.bss_table.toggle_mld_grouping();
+        self.reset_ie_table();
+    }
+
+    pub fn toggle_ess(&mut self) {
+        self.show_ess = !self.show_ess;
+    }
+
+    pub fn toggle_discovery(&mut self) {
+        if self.discovery.is_some() {
+            self.discovery = None;
+        } else {
+            let mut discovery = TableState::default();
+            if !self.bss_table.analysis().announced_6_ghz().is_empty() {
+                discovery.select(Some(0));
+            }
+            self.discovery = Some(discovery);
+        }
+    }
+
+    pub fn is_discovering(&self) -> bool {
+        self.discovery.is_some()
+    }
+
+    // Closes the discovery popup on the BSS table row of the selected announced BSS, if the
+    // scan picked it up directly
+    pub fn go_to_observed(&mut self) {
+        let bssid = match self.discovery.as_ref().and_then(TableState::selected) {
+            Some(selected) => match self.bss_table.analysis().announced_6_ghz().get(selected) {
+                Some(announced) => announced.bssid.clone(),
+                None => return,
+            },
+            None => return,
+        };
+
+        if self.bss_table.select_bssid(&bssid) {
+            self.discovery = None;
             self.bss_table.focus();
             self.ie_table.unfocus();
+            self.ie_table.unfocus_field_list();
+            self.reset_ie_table();
         }
     }
 
+    pub fn toggle_regulatory(&mut self) {
+        self.show_regulatory = !self.show_regulatory;
+    }
+
+    pub fn toggle_edca(&mut self) {
+        self.show_edca = !self.show_edca;
+    }
+
+    pub fn toggle_channel_summary(&mut self) {
+        self.show_channel_summary = !self.show_channel_summary;
+    }
+
+    pub fn toggle_mark(&mut self) {
+        self.bss_table.toggle_mark_selected();
+    }
+
+    // Opens the IE comparison of the two marked BSSs, or closes it if it's already open
+    pub fn toggle_ie_diff(&mut self) {
+        if self.ie_diff.is_some() {
+            self.ie_diff = None;
+        } else if let [left, right] = self.bss_table.marked_bss().as_slice() {
+            self.ie_diff = Some(IeDiffState::new(left, right));
+        }
+    }
+
+    // Starts locating the selected BSS, or goes back to the BSS table
+    pub fn toggle_locate(&mut self) {
+        if self.locate.is_some() {
+            self.locate = None;
+        } else if let Some(selected_bss) = self.bss_table.selected_bss() {
+            self.locate = Some(LocateState::new(selected_bss));
+        }
+    }
+
+    pub fn is_locating(&self) -> bool {
+        self.locate.is_some()
+    }
+
+    pub fn toggle_locate_bell(&mut self) {
+        if let Some(locate) = self.locate.as_mut() {
+            locate.toggle_bell();
+        }
+    }
+
+    pub fn reset_locate_peak(&mut self) {
+        if let Some(locate) = self.locate.as_mut() {
+            locate.reset_peak();
+        }
+    }
+
+    pub fn locate_bell_due(&mut self) -> bool {
+        self.locate
+            .as_mut()
+            .map(LocateState::bell_due)
+            .unwrap_or_default()
+    }
+
     pub fn render(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> io::Result<()> {
         terminal.draw(|frame| {
+            if let Some(locate) = self.locate.as_mut() {
+                frame.render_stateful_widget(LocateView::new(), frame.size(), locate);
+                return;
+            }
+
+            if let Some(ie_diff) = self.ie_diff.as_mut() {
+                frame.render_stateful_widget(IeDiffView::new(), frame.size(), ie_diff);
+                return;
+            }
+
             let chunks = self.layout.split(frame.size());
             frame.render_stateful_widget(BssTable::new(), chunks[0], &mut self.bss_table);
             frame.render_stateful_widget(IeTable::new(), chunks[1], &mut self.ie_table);
+
+            if self.show_interference_popup {
+                if let Some(selected_bss) = self.bss_table.selected_bss() {
+                    if let Some(interference) = self.bss_table.analysis().interference(selected_bss)
+                    {
+                        let bssid = selected_bss.bssid().to_string();
+                        frame.render_widget(
+                            InterferencePopup::new(&bssid, interference),
+                            widgets::centered_rect(80, 50, frame.size()),
+                        );
+                    }
+                }
+            }
+
+            if self.show_findings {
+                if let Some(selected_bss) = self.bss_table.selected_bss() {
+                    let bssid = selected_bss.bssid().to_string();
+                    frame.render_widget(
+                        FindingsPopup::new(
+                            &bssid,
+                            self.bss_table.analysis().findings(selected_bss),
+                        ),
+                        widgets::centered_rect(80, 50, frame.size()),
+                    );
+                }
+            }
+
+            if self.show_lint {
+                if let Some(selected_bss) = self.bss_table.selected_bss() {
+                    let bssid = selected_bss.bssid().to_string();
+                    frame.render_widget(
+                        LintPopup::new(
+                            &bssid,
+                            self.bss_table.analysis().lint_problems(selected_bss),
+                        ),
+                        widgets::centered_rect(80, 50, frame.size()),
+                    );
+                }
+            }
+
+            if self.show_ess {
+                frame.render_widget(
+                    EssPopup::new(self.bss_table.analysis().ess_deviations()),
+                    widgets::centered_rect(90, 60, frame.size()),
+                );
+            }
+
+            if self.show_regulatory {
+                if let Some(selected_bss) = self.bss_table.selected_bss() {
+                    frame.render_widget(
+                        RegulatoryPopup::new(selected_bss, self.bss_table.analysis()),
+                        widgets::centered_rect(70, 50, frame.size()),
+                    );
+                }
+            }
+
+            if self.show_edca {
+                if let Some(selected_bss) = self.bss_table.selected_bss() {
+                    frame.render_widget(
+                        EdcaPopup::new(selected_bss),
+                        widgets::centered_rect(80, 60, frame.size()),
+                    );
+                }
+            }
+
+            if self.show_channel_summary {
+                frame.render_widget(
+                    ChannelSummaryPopup::new(
+                        self.bss_table.analysis().channel_airtime(),
+                        self.bss_table.analysis().legacy_summary(),
+                    ),
+                    widgets::centered_rect(70, 60, frame.size()),
+                );
+            }
+
+            if let Some(discovery) = self.discovery.as_mut() {
+                frame.render_stateful_widget(
+                    DiscoveryPopup::new(self.bss_table.analysis().announced_6_ghz()),
+                    widgets::centered_rect(90, 60, frame.size()),
+                    discovery,
+                );
+            }
+
+            if let Some(search_input) = &self.search_input {
+                let size = frame.size();
+                let area = Rect::new(size.width / 5, size.height / 2, size.width * 3 / 5, 3);
+                frame.render_widget(Clear, area);
+                frame.render_widget(
+                    Paragraph::new(format!("/{}", search_input)).block(
+                        Block::default()
+                            .title("Search IEs (name, field text, field=value or #id)")
+                            .borders(Borders::ALL),
+                    ),
+                    area,
+                );
+            }
+
+            if self.show_change_log {
+                frame.render_widget(
+                    ChangeLogPopup::new(self.bss_table.analysis().changes().log()),
+                    widgets::centered_rect(90, 60, frame.size()),
+                );
+            }
         })
     }
 }
@@ -90,6 +465,19 @@ impl Default for App {
                 .direction(Direction::Vertical)
                 .margin(1)
                 .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref()),
+            show_interference_popup: false,
+            show_change_log: false,
+            show_findings: false,
+            show_lint: false,
+            show_ess: false,
+            discovery: None,
+            show_regulatory: false,
+            show_edca: false,
+            show_channel_summary: false,
+            ie_diff: None,
+            locate: None,
+            search_input: None,
+            alert_log: None,
         }
     }
 }
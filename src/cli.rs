@@ -0,0 +1,220 @@
+use crate::{
+    analysis::{PathLossModel, Severity},
+    decode::Encoding,
+};
+use std::path::PathBuf;
+
+pub const USAGE: &str = "Usage:
+    kawaiifi-cli [--import-iw PATH] [--allowlist PATH [--alert-log PATH]] [--features]
+                 [--path-loss-exponent N] [--tx-power DBM] [--format tui|text|json]
+    kawaiifi-cli decode [--format tui|text|json] [--hex | --base64] [--file PATH | IES]
+    kawaiifi-cli audit [--import-iw PATH] [--format text|json] [--fail-on SEVERITY]
+
+--import-iw shows the saved output of `iw dev <interface> scan` instead of scanning
+--allowlist flags BSSs that don't match the JSON list of legitimate networks, logging alerts to
+--alert-log (default: the allowlist path with a .log extension)
+--features adds the 11k/11v/11r, WMM, Wi-Fi 6/7 and WPA3 feature columns
+--path-loss-exponent and --tx-power tune the distance estimate: the exponent is 2 in free space
+and around 3 to 4 indoors (default 3), and the transmit power is assumed for APs that don't
+advertise one (default 20)
+IEs are read from the argument, the file or stdin, as hex, base64 or a wpa_cli bss ie= line.
+Input that is valid hex is read as hex unless --base64 is given
+audit exits with 3 when a finding is at least as severe as --fail-on (info, low, medium, high or
+critical, default low)";
+
+#[derive(Debug)]
+pub enum Command {
+    Scan(ScanOptions),
+    Decode(DecodeOptions),
+    Audit(AuditOptions),
+}
+
+#[derive(Debug)]
+pub struct ScanOptions {
+    pub source: ScanSource,
+    pub format: OutputFormat,
+    pub allowlist: Option<PathBuf>,
+    pub alert_log: Option<PathBuf>,
+    pub show_features: bool,
+    pub path_loss_model: PathLossModel,
+}
+
+#[derive(Debug)]
+pub struct AuditOptions {
+    pub source: ScanSource,
+    pub format: OutputFormat,
+    pub fail_on: Severity,
+}
+
+#[derive(Debug)]
+pub enum ScanSource {
+    Interface,
+    IwScan(PathBuf),
+}
+
+#[derive(Debug)]
+pub struct DecodeOptions {
+    pub input: Input,
+    pub encoding: Encoding,
+    pub format: OutputFormat,
+}
+
+#[derive(Debug)]
+pub enum Input {
+    Stdin,
+    File(PathBuf),
+    Argument(String),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum OutputFormat {
+    Tui,
+    Text,
+    Json,
+}
+
+impl Command {
+    pub fn from_args(args: impl Iterator<Item = String>) -> Result<Command, String> {
+        let mut args = args.peekable();
+        match args.peek().map(String::as_str) {
+            None => Ok(Command::Scan(ScanOptions::from_args(args)?)),
+            Some(arg) if arg.starts_with("--") => Ok(Command::Scan(ScanOptions::from_args(args)?)),
+            Some("decode") => {
+                args.next();
+                Ok(Command::Decode(DecodeOptions::from_args(args)?))
+            }
+            Some("audit") => {
+                args.next();
+                Ok(Command::Audit(AuditOptions::from_args(args)?))
+            }
+            Some(other) => Err(format!("Unknown command '{}'", other)),
+        }
+    }
+}
+
+impl ScanOptions {
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<ScanOptions, String> {
+        let mut options = ScanOptions {
+            source: ScanSource::Interface,
+            format: OutputFormat::Tui,
+            allowlist: None,
+            alert_log: None,
+            show_features: false,
+            path_loss_model: PathLossModel::default(),
+        };
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--format" => options.format = OutputFormat::from_arg(args.next())?,
+                "--import-iw" => match args.next() {
+                    Some(path) => options.source = ScanSource::IwScan(PathBuf::from(path)),
+                    None => return Err("--import-iw requires a path".to_string()),
+                },
+                "--allowlist" => match args.next() {
+                    Some(path) => options.allowlist = Some(PathBuf::from(path)),
+                    None => return Err("--allowlist requires a path".to_string()),
+                },
+                "--features" => options.show_features = true,
+                "--path-loss-exponent" => match args.next().and_then(|arg| arg.parse().ok()) {
+                    Some(exponent) if exponent > 0.0 && f64::is_finite(exponent) => {
+                        options.path_loss_model.exponent = exponent
+                    }
+                    _ => return Err("--path-loss-exponent requires a positive number".to_string()),
+                },
+                "--tx-power" => match args.next().and_then(|arg| arg.parse().ok()) {
+                    Some(tx_power_dbm) if f64::is_finite(tx_power_dbm) => {
+                        options.path_loss_model.tx_power_dbm = tx_power_dbm
+                    }
+                    _ => return Err("--tx-power requires a number of dBm".to_string()),
+                },
+                "--alert-log" => match args.next() {
+                    Some(path) => options.alert_log = Some(PathBuf::from(path)),
+                    None => return Err("--alert-log requires a path".to_string()),
+                },
+                _ => return Err(format!("Unknown option '{}'", arg)),
+            }
+        }
+
+        if options.alert_log.is_some() && options.allowlist.is_none() {
+            return Err("--alert-log requires --allowlist".to_string());
+        }
+
+        Ok(options)
+    }
+}
+
+impl AuditOptions {
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<AuditOptions, String> {
+        let mut options = AuditOptions {
+            source: ScanSource::Interface,
+            format: OutputFormat::Text,
+            fail_on: Severity::Low,
+        };
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--format" => match OutputFormat::from_arg(args.next())? {
+                    OutputFormat::Tui => return Err("audit supports text or json".to_string()),
+                    format => options.format = format,
+                },
+                "--import-iw" => match args.next() {
+                    Some(path) => options.source = ScanSource::IwScan(PathBuf::from(path)),
+                    None => return Err("--import-iw requires a path".to_string()),
+                },
+                "--fail-on" => {
+                    options.fail_on = match args.next().as_deref() {
+                        Some("info") => Severity::Info,
+                        Some("low") => Severity::Low,
+                        Some("medium") => Severity::Medium,
+                        Some("high") => Severity::High,
+                        Some("critical") => Severity::Critical,
+                        Some(other) => return Err(format!("Unknown severity '{}'", other)),
+                        None => return Err("--fail-on requires a severity".to_string()),
+                    }
+                }
+                _ => return Err(format!("Unknown option '{}'", arg)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+impl DecodeOptions {
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<DecodeOptions, String> {
+        let mut options = DecodeOptions {
+            input: Input::Stdin,
+            encoding: Encoding::Auto,
+            format: OutputFormat::Tui,
+        };
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--format" => options.format = OutputFormat::from_arg(args.next())?,
+                "--hex" => options.encoding = Encoding::Hex,
+                "--base64" => options.encoding = Encoding::Base64,
+                "--file" => match args.next() {
+                    Some(path) => options.input = Input::File(PathBuf::from(path)),
+                    None => return Err("--file requires a path".to_string()),
+                },
+                "-" => options.input = Input::Stdin,
+                _ if arg.starts_with("--") => return Err(format!("Unknown option '{}'", arg)),
+                _ => options.input = Input::Argument(arg),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+impl OutputFormat {
+    fn from_arg(arg: Option<String>) -> Result<OutputFormat, String> {
+        match arg.as_deref() {
+            Some("tui") => Ok(OutputFormat::Tui),
+            Some("text") => Ok(OutputFormat::Text),
+            Some("json") => Ok(OutputFormat::Json),
+            Some(other) => Err(format!("Unknown format '{}'", other)),
+            None => Err("--format requires one of tui, text or json".to_string()),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{
+    analysis::{Finding, ScanAnalysis},
+    cli::{AuditOptions, OutputFormat},
+    commands::scan,
+    decode::display_ssid,
+};
+use kawaiifi::Bss;
+use serde_json::{json, Value};
+use std::io;
+
+// Prints the findings of every BSS, returning whether any reach the --fail-on severity
+pub fn run(options: AuditOptions) -> io::Result<bool> {
+    let mut scan_results = scan::load(options.source)?;
+    scan_results.sort_by(|a, b| a.bssid().cmp(b.bssid()));
+
+    let mut analysis = ScanAnalysis::default();
+    analysis.update(&scan_results);
+
+    match options.format {
+        OutputFormat::Json => println!("{:#}", json_report(&scan_results, &analysis)),
+        _ => print!("{}", text_report(&scan_results, &analysis)),
+    }
+
+    let fail_on = options.fail_on;
+    Ok(scan_results.iter().any(|bss| {
+        analysis
+            .findings(bss)
+            .iter()
+            .any(|finding| finding.severity >= fail_on)
+    }))
+}
+
+fn text_report(scan_results: &[Bss], analysis: &ScanAnalysis) -> String {
+    let mut report = String::new();
+    for bss in scan_results {
+        report.push_str(&format!(
+            "{} \"{}\"\n",
+            bss.bssid(),
+            display_ssid(bss.ies()).unwrap_or_default()
+        ));
+        match analysis.findings(bss) {
+            [] => report.push_str("    No findings\n"),
+            findings => {
+                for finding in findings {
+                    report.push_str(&format!(
+                        "    [{}] {}: {}\n",
+                        finding.severity, finding.title, finding.detail
+                    ));
+                }
+            }
+        }
+    }
+    report
+}
+
+fn json_report(scan_results: &[Bss], analysis: &ScanAnalysis) -> Value {
+    Value::Array(
+        scan_results
+            .iter()
+            .map(|bss| {
+                json!({
+                    "bssid": bss.bssid().to_string(),
+                    "ssid": display_ssid(bss.ies()).unwrap_or_default(),
+                    "findings": analysis
+                        .findings(bss)
+                        .iter()
+                        .map(finding_json)
+                        .collect::<Vec<Value>>(),
+                })
+            })
+            .collect(),
+    )
+}
+
+fn finding_json(finding: &Finding) -> Value {
+    json!({
+        "severity": finding.severity.to_string(),
+        "title": finding.title,
+        "detail": finding.detail,
+    })
+}
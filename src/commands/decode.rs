@@ -0,0 +1,134 @@
+use crate::{
+    cli::{DecodeOptions, Input, OutputFormat},
+    decode::{self, Field},
+    terminal,
+    widgets::{IeTable, IeTableState},
+};
+use crossterm::event::{read, Event, KeyCode};
+use kawaiifi::{Ie, InformationElement};
+use serde_json::{json, Value};
+use std::{
+    fs,
+    io::{self, Read, Stdout},
+};
+use tui::{backend::CrosstermBackend, Terminal};
+
+pub fn run(options: DecodeOptions) -> io::Result<()> {
+    let text = match options.input {
+        Input::Stdin => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text)?;
+            text
+        }
+        Input::File(path) => fs::read_to_string(path)?,
+        Input::Argument(text) => text,
+    };
+
+    let ies = decode::bytes_from_text(&text, options.encoding)
+        .and_then(|bytes| decode::parse_ies(&bytes))
+        .map_err(|message| io::Error::new(io::ErrorKind::InvalidData, message))?;
+
+    match options.format {
+        OutputFormat::Tui => view(&ies),
+        OutputFormat::Text => {
+            print!("{}", text_report(&ies));
+            Ok(())
+        }
+        OutputFormat::Json => {
+            println!("{:#}", json_report(&ies));
+            Ok(())
+        }
+    }
+}
+
+fn view(ies: &[Ie]) -> io::Result<()> {
+    let mut ie_table = IeTableState::new();
+    ie_table.set_ies(None, ies);
+    ie_table.focus();
+
+    // Leave raw mode even if drawing or reading input fails
+    let mut terminal = terminal::enter()?;
+    let result = view_loop(&mut terminal, &mut ie_table);
+    terminal::leave();
+
+    result
+}
+
+fn view_loop(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    ie_table: &mut IeTableState,
+) -> io::Result<()> {
+    loop {
+        terminal.draw(|frame| {
+            frame.render_stateful_widget(IeTable::new(), frame.size(), ie_table);
+        })?;
+
+        let event = read().map_err(io::Error::other)?;
+        if let Event::Key(key_event) = event {
+            match key_event.code {
+                KeyCode::Char('q') => return Ok(()),
+                KeyCode::Up if ie_table.is_focused() => ie_table.select_previous(),
+                KeyCode::Down if ie_table.is_focused() => ie_table.select_next(),
+                KeyCode::Up => ie_table.select_previous_field(),
+                KeyCode::Down => ie_table.select_next_field(),
+                KeyCode::Char(' ') => ie_table.toggle_selected_field(),
+                KeyCode::Enter if ie_table.is_focused() => {
+                    ie_table.focus_field_list();
+                    ie_table.unfocus();
+                }
+                KeyCode::Enter => {
+                    ie_table.focus();
+                    ie_table.unfocus_field_list();
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+pub fn text_report(ies: &[Ie]) -> String {
+    let mut report = String::new();
+    for ie in ies {
+        report.push_str(&format!(
+            "{:>3}  {} ({} B)\n",
+            ie.id(),
+            decode::ie_name(ie),
+            ie.bytes().len()
+        ));
+        push_fields(&mut report, &decode::ie_fields(ie, ies), 1);
+    }
+    report
+}
+
+fn push_fields(report: &mut String, fields: &[Field], depth: usize) {
+    for field in fields {
+        report.push_str(&format!("{}{}\n", "    ".repeat(depth), field));
+        push_fields(report, &field.children, depth + 1);
+    }
+}
+
+pub fn json_report(ies: &[Ie]) -> Value {
+    Value::Array(
+        ies.iter()
+            .map(|ie| {
+                let fields = decode::ie_fields(ie, ies);
+                json!({
+                    "id": ie.id(),
+                    "name": decode::ie_name(ie),
+                    "length": ie.bytes().len(),
+                    "bytes": decode::hex(ie.bytes()),
+                    "fields": fields.iter().map(field_json).collect::<Vec<Value>>(),
+                })
+            })
+            .collect(),
+    )
+}
+
+fn field_json(field: &Field) -> Value {
+    json!({
+        "name": field.name,
+        "value": field.value,
+        "range": field.range.as_ref().map(|range| [range.start, range.end]),
+        "children": field.children.iter().map(field_json).collect::<Vec<Value>>(),
+    })
+}
@@ -0,0 +1,3 @@
+pub mod audit;
+pub mod decode;
+pub mod scan;
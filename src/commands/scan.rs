@@ -0,0 +1,134 @@
+use crate::{
+    analysis::{AlertLog, Allowlist},
+    cli::{OutputFormat, ScanOptions, ScanSource},
+    iw,
+    widgets::BssTableState,
+};
+use kawaiifi::Bss;
+use serde_json::{Map, Value};
+use std::{fs, io, path::Path};
+use unicode_width::UnicodeWidthStr;
+
+pub fn import_iw(path: &Path) -> io::Result<Vec<Bss>> {
+    iw::parse_scan(&fs::read_to_string(path)?)
+        .map_err(|message| io::Error::new(io::ErrorKind::InvalidData, message))
+}
+
+// Runs a single scan, or reads one from a file
+pub fn load(source: ScanSource) -> io::Result<Vec<Bss>> {
+    match source {
+        ScanSource::Interface => {
+            let interfaces = kawaiifi::interfaces();
+            let first_interface = interfaces.iter().next().ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "Could not find any Wi-Fi interfaces",
+                )
+            })?;
+            Ok(first_interface
+                .scan()
+                .map_err(|e| io::Error::other(format!("{:?}", e)))?
+                .into_iter()
+                .collect())
+        }
+        ScanSource::IwScan(path) => import_iw(&path),
+    }
+}
+
+pub fn load_allowlist(options: &ScanOptions) -> io::Result<Option<(Allowlist, AlertLog)>> {
+    let path = match &options.allowlist {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+
+    let allowlist = Allowlist::from_json(&fs::read_to_string(path)?)
+        .map_err(|message| io::Error::new(io::ErrorKind::InvalidData, message))?;
+    let alert_log = AlertLog::open(
+        &options
+            .alert_log
+            .clone()
+            .unwrap_or_else(|| path.with_extension("log")),
+    )?;
+
+    Ok(Some((allowlist, alert_log)))
+}
+
+// Prints a single scan as a table or JSON instead of starting the TUI
+pub fn run_headless(options: ScanOptions) -> io::Result<()> {
+    let allowlist = load_allowlist(&options)?;
+    let format = options.format;
+    let scan_results = load(options.source)?;
+
+    let mut bss_table = BssTableState::new();
+    bss_table.set_path_loss_model(options.path_loss_model);
+    if options.show_features {
+        bss_table.toggle_feature_columns();
+    }
+    if let Some((allowlist, mut alert_log)) = allowlist {
+        bss_table.set_allowlist(allowlist);
+        bss_table.set_scan_results(scan_results);
+        alert_log.write(bss_table.analysis().new_rogue_alerts())?;
+    } else {
+        bss_table.set_scan_results(scan_results);
+    }
+
+    match format {
+        OutputFormat::Json => println!("{:#}", json_report(&bss_table)),
+        _ => print!("{}", text_report(&bss_table)),
+    }
+
+    Ok(())
+}
+
+pub fn text_report(bss_table: &BssTableState) -> String {
+    let header = bss_table
+        .header()
+        .iter()
+        .map(|column| column.to_string())
+        .collect::<Vec<String>>();
+    let rows = bss_table.rows().collect::<Vec<&[String]>>();
+
+    let widths = header
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            rows.iter()
+                .map(|row| row[i].width())
+                .chain(Some(column.width()))
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<usize>>();
+
+    let mut report = String::new();
+    for row in Some(header.as_slice()).into_iter().chain(rows) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            // Pad by display width, as wide characters take up two columns
+            .map(|(value, width)| format!("{}{}", value, " ".repeat(width - value.width())))
+            .collect::<Vec<String>>()
+            .join("  ");
+        report.push_str(line.trim_end());
+        report.push('\n');
+    }
+    report
+}
+
+pub fn json_report(bss_table: &BssTableState) -> Value {
+    Value::Array(
+        bss_table
+            .rows()
+            .map(|row| {
+                Value::Object(
+                    bss_table
+                        .header()
+                        .iter()
+                        .zip(row)
+                        .map(|(column, value)| (column.to_string(), Value::from(value.as_str())))
+                        .collect::<Map<String, Value>>(),
+                )
+            })
+            .collect(),
+    )
+}
@@ -0,0 +1,88 @@
+use crate::decode::{self, read_u16, Field};
+use kawaiifi::Ie;
+
+pub const BSS_LOAD_ID: u8 = 11;
+pub const HE_BSS_LOAD_EXT_ID: u8 = 47;
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct BssLoad {
+    pub station_count: Option<u16>,
+    // Percentage of time the AP sensed the medium busy, scaled to 0-255
+    pub channel_utilization: Option<u8>,
+    // Remaining medium time available via explicit admission control, in units of 32 us/s
+    pub available_admission_capacity: Option<u16>,
+}
+
+impl BssLoad {
+    // Prefers the BSS Load element and falls back to the HE BSS Load element.
+    // 802.11be doesn't define an EHT specific load element, EHT APs report via HE BSS Load.
+    pub fn from_ies(ies: &[Ie]) -> Option<BssLoad> {
+        if let Some(bytes) = decode::find_ie(ies, BSS_LOAD_ID) {
+            return Some(BssLoad {
+                station_count: read_u16(bytes, 0),
+                channel_utilization: bytes.get(2).copied(),
+                available_admission_capacity: read_u16(bytes, 3),
+            });
+        }
+
+        // HE STA Count (2), Utilization (1), Frequency Underutilization (1),
+        // Spatial Stream Underutilization (1)
+        decode::find_ext_ie(ies, HE_BSS_LOAD_EXT_ID).map(|bytes| BssLoad {
+            station_count: read_u16(bytes, 0),
+            channel_utilization: bytes.get(2).copied(),
+            available_admission_capacity: None,
+        })
+    }
+
+    pub fn channel_utilization_percent(&self) -> Option<f64> {
+        self.channel_utilization
+            .map(|utilization| utilization as f64 * 100.0 / 255.0)
+    }
+
+    pub fn available_admission_capacity_us(&self) -> Option<u32> {
+        self.available_admission_capacity
+            .map(|capacity| capacity as u32 * 32)
+    }
+}
+
+pub fn fields(bytes: &[u8]) -> Vec<Field> {
+    let mut fields = Vec::new();
+    if let Some(station_count) = read_u16(bytes, 0) {
+        fields.push(Field::new("Station Count", station_count).at(0..2));
+    }
+    if let Some(utilization) = bytes.get(2) {
+        fields.push(Field::new("Channel Utilization", utilization_text(*utilization)).at(2..3));
+    }
+    if let Some(capacity) = read_u16(bytes, 3) {
+        fields.push(
+            Field::new(
+                "Available Admission Capacity",
+                format!("{} us/s", capacity as u32 * 32),
+            )
+            .at(3..5),
+        );
+    }
+    fields
+}
+
+// The fields of an HE BSS Load element after its extension ID
+pub fn he_fields(bytes: &[u8]) -> Vec<Field> {
+    let mut fields = Vec::new();
+    if let Some(station_count) = read_u16(bytes, 0) {
+        fields.push(Field::new("HE Station Count", station_count).at(0..2));
+    }
+    for (at, name) in &[
+        (2, "Utilization"),
+        (3, "Frequency Underutilization"),
+        (4, "Spatial Stream Underutilization"),
+    ] {
+        if let Some(value) = bytes.get(*at) {
+            fields.push(Field::new(name, utilization_text(*value)).at(*at..at + 1));
+        }
+    }
+    fields
+}
+
+fn utilization_text(value: u8) -> String {
+    format!("{} ({:.0}%)", value, value as f64 * 100.0 / 255.0)
+}
@@ -0,0 +1,182 @@
+use crate::decode::{self, read_u16, Field};
+use kawaiifi::Ie;
+
+pub const MOBILITY_DOMAIN_ID: u8 = 54;
+pub const RM_ENABLED_CAPABILITIES_ID: u8 = 70;
+pub const EXTENDED_CAPABILITIES_ID: u8 = 127;
+
+pub const EXTENDED_CAPABILITY_BSS_TRANSITION: usize = 19;
+
+const RM_NEIGHBOR_REPORT: u8 = 0x02;
+
+const FT_CAPABILITY_AND_POLICY: &[(u32, &str)] = &[
+    (0x01, "Fast BSS Transition over DS"),
+    (0x02, "Resource Request Protocol Capability"),
+];
+
+const EXTENDED_CAPABILITIES: &[(usize, &str)] = &[
+    (0, "20/40 BSS Coexistence Management Support"),
+    (2, "Extended Channel Switching"),
+    (4, "PSMP Capability"),
+    (6, "S-PSMP Support"),
+    (7, "Event"),
+    (8, "Diagnostics"),
+    (9, "Multicast Diagnostics"),
+    (10, "Location Tracking"),
+    (11, "FMS"),
+    (12, "Proxy ARP Service"),
+    (13, "Collocated Interference Reporting"),
+    (14, "Civic Location"),
+    (15, "Geospatial Location"),
+    (16, "TFS"),
+    (17, "WNM Sleep Mode"),
+    (18, "TIM Broadcast"),
+    (19, "BSS Transition"),
+    (20, "QoS Traffic Capability"),
+    (21, "AC Station Count"),
+    (22, "Multiple BSSID"),
+    (23, "Timing Measurement"),
+    (24, "Channel Usage"),
+    (25, "SSID List"),
+    (26, "DMS"),
+    (27, "UTC TSF Offset"),
+    (28, "TPU Buffer STA Support"),
+    (29, "TDLS Peer PSM Support"),
+    (30, "TDLS Channel Switching"),
+    (31, "Interworking"),
+    (32, "QoS Map"),
+    (33, "EBR"),
+    (34, "SSPN Interface"),
+    (36, "MSGCF Capability"),
+    (37, "TDLS Support"),
+    (38, "TDLS Prohibited"),
+    (39, "TDLS Channel Switching Prohibited"),
+    (40, "Reject Unadmitted Frame"),
+    (44, "Identifier Location"),
+    (45, "U-APSD Coexistence"),
+    (46, "WNM Notification"),
+    (47, "QAB Capability"),
+    (48, "UTF-8 SSID"),
+    (49, "QMF Activated"),
+    (50, "QMF Reconfiguration Activated"),
+    (51, "Robust AV Streaming"),
+    (52, "Advanced GCR"),
+    (53, "Mesh GCR"),
+    (54, "SCS"),
+    (55, "QLoad Report"),
+    (56, "Alternate EDCA"),
+    (57, "Unprotected TXOP Negotiation"),
+    (58, "Protected TXOP Negotiation"),
+    (61, "TDLS Wider Bandwidth"),
+    (62, "Operating Mode Notification"),
+    (65, "Channel Schedule Management"),
+    (66, "Geodatabase Inband Enabling Signal"),
+    (67, "Network Channel Control"),
+    (68, "White Space Map"),
+    (69, "Channel Availability Query"),
+    (70, "FTM Responder"),
+    (71, "FTM Initiator"),
+    (72, "FILS Capability"),
+    (73, "Extended Spectrum Management Capable"),
+    (74, "Future Channel Guidance"),
+    (75, "PAD"),
+    (77, "TWT Requester Support"),
+    (78, "TWT Responder Support"),
+    (79, "OBSS Narrow Bandwidth RU in OFDMA Tolerance Support"),
+    (80, "Complete List of NonTxBSSID Profiles"),
+    (81, "SAE Password Identifiers In Use"),
+    (82, "SAE Passwords Used Exclusively"),
+    (84, "Beacon Protection Enabled"),
+    (85, "Mirrored SCS"),
+    (87, "Local MAC Address Policy"),
+];
+
+const RM_ENABLED_CAPABILITIES: &[(usize, &str)] = &[
+    (0, "Link Measurement"),
+    (1, "Neighbor Report"),
+    (2, "Parallel Measurements"),
+    (3, "Repeated Measurements"),
+    (4, "Beacon Passive Measurement"),
+    (5, "Beacon Active Measurement"),
+    (6, "Beacon Table Measurement"),
+    (7, "Beacon Measurement Reporting Conditions"),
+    (8, "Frame Measurement"),
+    (9, "Channel Load Measurement"),
+    (10, "Noise Histogram Measurement"),
+    (11, "Statistics Measurement"),
+    (12, "LCI Measurement"),
+    (13, "LCI Azimuth"),
+    (14, "Transmit Stream/Category Measurement"),
+    (15, "Triggered Transmit Stream/Category Measurement"),
+    (16, "AP Channel Report"),
+    (17, "RM MIB"),
+    (27, "Measurement Pilot Transmission Information"),
+    (28, "Neighbor Report TSF Offset"),
+    (29, "RCPI Measurement"),
+    (30, "RSNI Measurement"),
+    (31, "BSS Average Access Delay"),
+    (32, "BSS Available Admission Capacity"),
+    (33, "Antenna"),
+    (34, "FTM Range Report"),
+    (35, "Civic Location Measurement"),
+];
+
+// The 802.11r Mobility Domain element
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct MobilityDomain {
+    pub mdid: u16,
+    pub ft_over_ds: bool,
+}
+
+impl MobilityDomain {
+    pub fn from_ies(ies: &[Ie]) -> Option<MobilityDomain> {
+        let bytes = decode::find_ie(ies, MOBILITY_DOMAIN_ID)?;
+        Some(MobilityDomain {
+            mdid: read_u16(bytes, 0)?,
+            ft_over_ds: bytes.get(2)? & 0x01 != 0,
+        })
+    }
+}
+
+// Whether a bit of the Extended Capabilities element is set, counting from bit 0 of octet 0
+pub fn extended_capability(ies: &[Ie], bit: usize) -> bool {
+    decode::find_ie(ies, EXTENDED_CAPABILITIES_ID)
+        .and_then(|bytes| bytes.get(bit / 8))
+        .map(|byte| byte & (1 << (bit % 8)) != 0)
+        .unwrap_or_default()
+}
+
+// 802.11k radio measurement is advertised by the RM Enabled Capabilities element
+pub fn radio_measurement(ies: &[Ie]) -> bool {
+    decode::find_ie(ies, RM_ENABLED_CAPABILITIES_ID).is_some()
+}
+
+pub fn neighbor_report(ies: &[Ie]) -> bool {
+    decode::find_ie(ies, RM_ENABLED_CAPABILITIES_ID)
+        .and_then(|bytes| bytes.first())
+        .map(|byte| byte & RM_NEIGHBOR_REPORT != 0)
+        .unwrap_or_default()
+}
+
+pub fn mobility_domain_fields(bytes: &[u8]) -> Vec<Field> {
+    let mut fields = Vec::new();
+    if let Some(mdid) = read_u16(bytes, 0) {
+        fields.push(Field::new("Mobility Domain Identifier", format!("{:#06x}", mdid)).at(0..2));
+    }
+    if let Some(policy) = bytes.get(2) {
+        fields.push(
+            Field::new("FT Capability and Policy", format!("{:#04x}", policy))
+                .at(2..3)
+                .with_children(Field::flags(*policy as u32, FT_CAPABILITY_AND_POLICY, 2..3)),
+        );
+    }
+    fields
+}
+
+pub fn extended_capabilities_fields(bytes: &[u8]) -> Vec<Field> {
+    Field::bitmap(bytes, EXTENDED_CAPABILITIES, 0)
+}
+
+pub fn rm_enabled_capabilities_fields(bytes: &[u8]) -> Vec<Field> {
+    Field::bitmap(bytes, RM_ENABLED_CAPABILITIES, 0)
+}
@@ -0,0 +1,101 @@
+use crate::decode::{
+    self,
+    capabilities::{self, EXTENDED_CAPABILITY_BSS_TRANSITION},
+    vendor::{MICROSOFT_OUI, WMM_OUI_TYPE},
+    BasicMultiLink, MobilityDomain, Rsn, WmmParameters,
+};
+use kawaiifi::Ie;
+use std::fmt::Display;
+
+const EXTENDED_CAPABILITY_TWT_RESPONDER: usize = 78;
+
+const HE_CAPABILITIES_EXT_ID: u8 = 35;
+const HE_OPERATION_EXT_ID: u8 = 36;
+
+const HE_MAC_TWT_RESPONDER: u8 = 0x04;
+const HE_BSS_COLOR_DISABLED: u8 = 0x80;
+const HE_BSS_COLOR_MASK: u8 = 0x3f;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Feature {
+    RadioMeasurement,
+    BssTransition,
+    FastTransition,
+    Wmm,
+    Uapsd,
+    Twt,
+    Ofdma,
+    BssColoring,
+    Mlo,
+    Wpa3,
+}
+
+impl Feature {
+    pub const ALL: [Feature; 10] = [
+        Feature::RadioMeasurement,
+        Feature::BssTransition,
+        Feature::FastTransition,
+        Feature::Wmm,
+        Feature::Uapsd,
+        Feature::Twt,
+        Feature::Ofdma,
+        Feature::BssColoring,
+        Feature::Mlo,
+        Feature::Wpa3,
+    ];
+
+    // Whether the IEs show the feature as enabled rather than only supported by the chipset
+    pub fn is_enabled(&self, ies: &[Ie]) -> bool {
+        match self {
+            Feature::RadioMeasurement => capabilities::radio_measurement(ies),
+            Feature::BssTransition => {
+                capabilities::extended_capability(ies, EXTENDED_CAPABILITY_BSS_TRANSITION)
+            }
+            Feature::FastTransition => {
+                MobilityDomain::from_ies(ies).is_some()
+                    || Rsn::from_ies(ies)
+                        .map(|rsn| rsn.is_ft())
+                        .unwrap_or_default()
+            }
+            Feature::Wmm => decode::find_vendor_ie(ies, MICROSOFT_OUI, WMM_OUI_TYPE).is_some(),
+            Feature::Uapsd => WmmParameters::from_ies(ies)
+                .map(|wmm| wmm.uapsd())
+                .unwrap_or_default(),
+            Feature::Twt => {
+                capabilities::extended_capability(ies, EXTENDED_CAPABILITY_TWT_RESPONDER)
+                    || decode::find_ext_ie(ies, HE_CAPABILITIES_EXT_ID)
+                        .and_then(|bytes| bytes.first())
+                        .map(|mac_capabilities| mac_capabilities & HE_MAC_TWT_RESPONDER != 0)
+                        .unwrap_or_default()
+            }
+            // OFDMA is mandatory for HE APs
+            Feature::Ofdma => decode::find_ext_ie(ies, HE_CAPABILITIES_EXT_ID).is_some(),
+            // BSS Color Information follows the three octets of HE Operation Parameters
+            Feature::BssColoring => decode::find_ext_ie(ies, HE_OPERATION_EXT_ID)
+                .and_then(|bytes| bytes.get(3))
+                .map(|color| color & HE_BSS_COLOR_DISABLED == 0 && color & HE_BSS_COLOR_MASK != 0)
+                .unwrap_or_default(),
+            Feature::Mlo => BasicMultiLink::from_ies(ies).is_some(),
+            Feature::Wpa3 => Rsn::from_ies(ies)
+                .map(|rsn| rsn.is_sae() || rsn.has_akm(decode::rsn::AKM_8021X_SUITE_B_192))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Display for Feature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Feature::RadioMeasurement => write!(f, "11k"),
+            Feature::BssTransition => write!(f, "11v"),
+            Feature::FastTransition => write!(f, "11r"),
+            Feature::Wmm => write!(f, "WMM"),
+            Feature::Uapsd => write!(f, "U-APSD"),
+            Feature::Twt => write!(f, "TWT"),
+            Feature::Ofdma => write!(f, "OFDMA"),
+            Feature::BssColoring => write!(f, "BSS Color"),
+            Feature::Mlo => write!(f, "MLO"),
+            Feature::Wpa3 => write!(f, "WPA3"),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::decode::ssid::escape_text;
+use std::{fmt::Display, ops::Range};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub value: String,
+    // The bytes of the IE body this field was decoded from, if known
+    pub range: Option<Range<usize>>,
+    // Sub-elements, bitfields and other nested structures
+    pub children: Vec<Field>,
+}
+
+impl Field {
+    pub fn new(name: &str, value: impl ToString) -> Field {
+        Field {
+            name: name.to_string(),
+            value: value.to_string(),
+            range: None,
+            children: Vec::new(),
+        }
+    }
+
+    // One child per flag, each showing whether its bit is set in the value
+    pub fn flags(value: u32, flags: &[(u32, &str)], range: Range<usize>) -> Vec<Field> {
+        flags
+            .iter()
+            .map(|(bit, name)| Field::new(name, value & bit != 0).at(range.clone()))
+            .collect()
+    }
+
+    // One child per octet of a capability bitmap, each holding the flags of the named bits in it
+    pub fn bitmap(bytes: &[u8], bits: &[(usize, &str)], start: usize) -> Vec<Field> {
+        bytes
+            .iter()
+            .enumerate()
+            .map(|(i, byte)| {
+                let at = start + i;
+                Field::new(&format!("Octet {}", i), format!("{:#04x}", byte))
+                    .at(at..at + 1)
+                    .with_children(
+                        bits.iter()
+                            .filter(|(bit, _)| bit / 8 == i)
+                            .map(|(bit, name)| {
+                                Field::new(name, byte & (1 << (bit % 8)) != 0).at(at..at + 1)
+                            })
+                            .collect(),
+                    )
+            })
+            .collect()
+    }
+
+    pub fn at(mut self, range: Range<usize>) -> Field {
+        self.range = Some(range);
+        self
+    }
+
+    pub fn with_children(mut self, children: Vec<Field>) -> Field {
+        self.children = children;
+        self
+    }
+
+    // Shifts the byte range of a field decoded from a slice of the IE body
+    pub fn offset(mut self, by: usize) -> Field {
+        self.range = self.range.map(|range| range.start + by..range.end + by);
+        self.children = self
+            .children
+            .into_iter()
+            .map(|child| child.offset(by))
+            .collect();
+        self
+    }
+
+    // A field that kawaiifi has already formatted as a single line, which may hold text from the
+    // IE
+    pub fn text(text: impl ToString) -> Field {
+        Field {
+            name: escape_text(&text.to_string()),
+            value: String::new(),
+            range: None,
+            children: Vec::new(),
+        }
+    }
+}
+
+impl Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.value.is_empty() {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{}: {}", self.name, self.value)
+        }
+    }
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+pub fn mac(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<String>>()
+        .join(":")
+}
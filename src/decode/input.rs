@@ -0,0 +1,74 @@
+use kawaiifi::Ie;
+
+// How IE bytes are written out. Some base64 strings are also valid hex, so the encoding can be
+// given rather than guessed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Encoding {
+    Auto,
+    Hex,
+    Base64,
+}
+
+// Accepts hex (with or without separators), base64 or a wpa_cli bss "ie=" line
+pub fn bytes_from_text(text: &str, encoding: Encoding) -> Result<Vec<u8>, String> {
+    let text = text
+        .lines()
+        .map(str::trim)
+        .find_map(|line| line.strip_prefix("ie="))
+        .unwrap_or(text);
+
+    let hex = text
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ':' && *c != '-')
+        .collect::<String>();
+    let hex = hex.strip_prefix("0x").unwrap_or(&hex);
+    let is_hex = hex.len() % 2 == 0 && hex.chars().all(|c| c.is_ascii_hexdigit());
+
+    if hex.is_empty() {
+        Err("No IE bytes given".to_string())
+    } else if encoding == Encoding::Hex || (encoding == Encoding::Auto && is_hex) {
+        if !is_hex {
+            return Err("IEs aren't valid hex".to_string());
+        }
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|e| e.to_string()))
+            .collect()
+    } else {
+        let base64 = text
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+        base64::decode(&base64).map_err(|_| match encoding {
+            Encoding::Base64 => "IEs aren't valid base64".to_string(),
+            _ => "IEs must be given as hex or base64".to_string(),
+        })
+    }
+}
+
+// Splits a buffer of concatenated IEs into kawaiifi IEs
+pub fn parse_ies(bytes: &[u8]) -> Result<Vec<Ie>, String> {
+    let mut ies = Vec::new();
+    let mut offset = 0;
+
+    while offset < bytes.len() {
+        let id = bytes[offset];
+        let length = *bytes
+            .get(offset + 1)
+            .ok_or_else(|| format!("IE {} at offset {} has no length", id, offset))?
+            as usize;
+        let body = bytes.get(offset + 2..offset + 2 + length).ok_or_else(|| {
+            format!(
+                "IE {} at offset {} claims {} bytes but only {} remain",
+                id,
+                offset,
+                length,
+                bytes.len() - offset - 2
+            )
+        })?;
+        ies.push(Ie::from_bytes(id, body));
+        offset += 2 + length;
+    }
+
+    Ok(ies)
+}
@@ -0,0 +1,146 @@
+mod bss_load;
+pub mod capabilities;
+mod features;
+mod field;
+mod input;
+pub mod multi_link;
+mod phy;
+mod rates;
+pub mod regulatory;
+pub mod rnr;
+pub mod rsn;
+mod ssid;
+pub mod vendor;
+mod wmm;
+
+pub use bss_load::{BssLoad, BSS_LOAD_ID, HE_BSS_LOAD_EXT_ID};
+pub use capabilities::MobilityDomain;
+pub use features::Feature;
+pub use field::{hex, Field};
+pub use input::{bytes_from_text, parse_ies, Encoding};
+pub use multi_link::BasicMultiLink;
+pub use phy::TIM_ID;
+pub use rates::{min_basic_rate, supported_rates};
+pub use regulatory::{Country, TpcReport};
+pub use rsn::Rsn;
+pub use ssid::{display_ssid, truncate_to_width, SSID_ID};
+pub use wmm::{AcParameters, AccessCategory, MuAcParameters, MuEdcaParameters, WmmParameters};
+
+use kawaiifi::{Ie, InformationElement};
+
+pub const VENDOR_SPECIFIC_ID: u8 = 221;
+pub const ELEMENT_ID_EXTENSION: u8 = 255;
+
+// kawaiifi names every vendor specific IE the same, so name them by OUI instead
+pub fn ie_name(ie: &Ie) -> String {
+    if ie.id() == VENDOR_SPECIFIC_ID {
+        vendor::name(ie.bytes())
+    } else {
+        ie.name().to_string()
+    }
+}
+
+// The decoded fields of an IE. The other IEs of the BSS are needed by the fields that depend on
+// them, e.g. the SSID depends on whether the Extended Capabilities declare it UTF-8.
+pub fn ie_fields(ie: &Ie, ies: &[Ie]) -> Vec<Field> {
+    match ie.id() {
+        VENDOR_SPECIFIC_ID => vendor::fields(ie.bytes()),
+        rnr::REDUCED_NEIGHBOR_REPORT_ID => rnr::fields(ie.bytes()),
+        ssid::SSID_ID => ssid::fields(ie.bytes(), ies),
+        rates::SUPPORTED_RATES_ID | rates::EXTENDED_SUPPORTED_RATES_ID => rates::fields(ie.bytes()),
+        phy::DS_PARAMETER_SET_ID => phy::ds_parameter_set_fields(ie.bytes()),
+        phy::TIM_ID => phy::tim_fields(ie.bytes()),
+        regulatory::COUNTRY_ID => regulatory::country_fields(ie.bytes()),
+        bss_load::BSS_LOAD_ID => bss_load::fields(ie.bytes()),
+        regulatory::POWER_CONSTRAINT_ID => regulatory::power_constraint_fields(ie.bytes()),
+        regulatory::TPC_REPORT_ID => regulatory::tpc_report_fields(ie.bytes()),
+        phy::HT_CAPABILITIES_ID => phy::ht_capabilities_fields(ie.bytes()),
+        rsn::RSN_ID => rsn::fields(ie.bytes()),
+        capabilities::MOBILITY_DOMAIN_ID => capabilities::mobility_domain_fields(ie.bytes()),
+        phy::HT_OPERATION_ID => phy::ht_operation_fields(ie.bytes()),
+        capabilities::RM_ENABLED_CAPABILITIES_ID => {
+            capabilities::rm_enabled_capabilities_fields(ie.bytes())
+        }
+        capabilities::EXTENDED_CAPABILITIES_ID => {
+            capabilities::extended_capabilities_fields(ie.bytes())
+        }
+        phy::VHT_CAPABILITIES_ID => phy::vht_capabilities_fields(ie.bytes()),
+        phy::VHT_OPERATION_ID => phy::vht_operation_fields(ie.bytes()),
+        ELEMENT_ID_EXTENSION => match ext_fields(ie.bytes()) {
+            Some(fields) => fields,
+            None => ie.information_fields().iter().map(Field::text).collect(),
+        },
+        _ => ie.information_fields().iter().map(Field::text).collect(),
+    }
+}
+
+// Extension element fields are decoded without the extension ID, then offset past it
+fn ext_fields(bytes: &[u8]) -> Option<Vec<Field>> {
+    let (ext_id, body) = bytes.split_first()?;
+    let fields = match *ext_id {
+        phy::HE_CAPABILITIES_EXT_ID => phy::he_capabilities_fields(body),
+        phy::HE_OPERATION_EXT_ID => phy::he_operation_fields(body),
+        bss_load::HE_BSS_LOAD_EXT_ID => bss_load::he_fields(body),
+        multi_link::MULTI_LINK_EXT_ID => multi_link::fields(body),
+        _ => return None,
+    };
+    Some(fields.into_iter().map(|field| field.offset(1)).collect())
+}
+
+// Identifies which element an IE is beyond its element ID, so that e.g. the HE and EHT
+// extension elements or WMM and WPS vendor elements are never mistaken for one another
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct IeKey {
+    pub id: u8,
+    pub ext_id: Option<u8>,
+    pub vendor: Option<[u8; 4]>,
+}
+
+pub fn ie_key(ie: &Ie) -> IeKey {
+    let bytes = ie.bytes();
+    IeKey {
+        id: ie.id(),
+        ext_id: if ie.id() == ELEMENT_ID_EXTENSION {
+            bytes.first().copied()
+        } else {
+            None
+        },
+        vendor: if ie.id() == VENDOR_SPECIFIC_ID && bytes.len() >= 4 {
+            Some([bytes[0], bytes[1], bytes[2], bytes[3]])
+        } else {
+            None
+        },
+    }
+}
+
+// Returns the body of the first IE with the given element ID
+pub fn find_ie(ies: &[Ie], id: u8) -> Option<&[u8]> {
+    ies.iter().find(|ie| ie.id() == id).map(|ie| ie.bytes())
+}
+
+// Returns the body of the first extension IE with the given element ID extension,
+// without the leading extension ID octet
+pub fn find_ext_ie(ies: &[Ie], ext_id: u8) -> Option<&[u8]> {
+    ies.iter()
+        .filter(|ie| ie.id() == ELEMENT_ID_EXTENSION)
+        .map(|ie| ie.bytes())
+        .find(|bytes| bytes.first() == Some(&ext_id))
+        .map(|bytes| &bytes[1..])
+}
+
+// Returns the body of the first vendor specific IE with the given OUI and OUI type,
+// without the OUI and OUI type octets
+pub fn find_vendor_ie(ies: &[Ie], oui: [u8; 3], oui_type: u8) -> Option<&[u8]> {
+    ies.iter()
+        .filter(|ie| ie.id() == VENDOR_SPECIFIC_ID)
+        .map(|ie| ie.bytes())
+        .find(|bytes| bytes.len() >= 4 && bytes[0..3] == oui && bytes[3] == oui_type)
+        .map(|bytes| &bytes[4..])
+}
+
+pub(crate) fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes([
+        *bytes.get(offset)?,
+        *bytes.get(offset + 1)?,
+    ]))
+}
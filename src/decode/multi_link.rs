@@ -0,0 +1,228 @@
+use crate::decode::{
+    self,
+    field::{hex, mac},
+    read_u16, Field,
+};
+use kawaiifi::Ie;
+
+pub const MULTI_LINK_EXT_ID: u8 = 107;
+
+const MULTI_LINK_TYPES: &[&str] = &[
+    "Basic",
+    "Probe Request",
+    "Reconfiguration",
+    "TDLS",
+    "Priority Access",
+];
+
+// Presence bitmap of the Basic Multi-Link element, in the order the fields appear
+const LINK_ID_INFO_PRESENT: u16 = 0x0010;
+const BSS_PARAMETERS_CHANGE_COUNT_PRESENT: u16 = 0x0020;
+const MEDIUM_SYNCHRONIZATION_DELAY_PRESENT: u16 = 0x0040;
+const EML_CAPABILITIES_PRESENT: u16 = 0x0080;
+const MLD_CAPABILITIES_PRESENT: u16 = 0x0100;
+const AP_MLD_ID_PRESENT: u16 = 0x0200;
+const EXTENDED_MLD_CAPABILITIES_PRESENT: u16 = 0x0400;
+
+const PER_STA_PROFILE_SUBELEMENT_ID: u8 = 0;
+
+// The Common Info of a Basic Multi-Link element, which every affiliated AP of an AP MLD sends
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BasicMultiLink {
+    pub mld_mac: String,
+    pub link_id: Option<u8>,
+}
+
+impl BasicMultiLink {
+    pub fn from_ies(ies: &[Ie]) -> Option<BasicMultiLink> {
+        BasicMultiLink::parse(decode::find_ext_ie(ies, MULTI_LINK_EXT_ID)?)
+    }
+
+    // Parses the body of the element following the element ID extension octet
+    pub fn parse(bytes: &[u8]) -> Option<BasicMultiLink> {
+        let control = read_u16(bytes, 0)?;
+        if control & 0x07 != 0 {
+            return None;
+        }
+
+        let multi_link = BasicMultiLink {
+            // Skip the Common Info Length octet
+            mld_mac: mac(bytes.get(3..9)?),
+            // Link ID Info is the first of the optional fields
+            link_id: if control & LINK_ID_INFO_PRESENT != 0 {
+                bytes.get(9).map(|id| id & 0x0f)
+            } else {
+                None
+            },
+        };
+
+        Some(multi_link)
+    }
+}
+
+pub fn fields(bytes: &[u8]) -> Vec<Field> {
+    let control = match read_u16(bytes, 0) {
+        Some(control) => control,
+        None => return vec![Field::new("Data", hex(bytes)).at(0..bytes.len())],
+    };
+    let multi_link_type = (control & 0x07) as usize;
+
+    let mut fields = vec![Field::new(
+        "Type",
+        MULTI_LINK_TYPES
+            .get(multi_link_type)
+            .copied()
+            .unwrap_or("Reserved"),
+    )
+    .at(0..2)];
+
+    if multi_link_type != 0 {
+        fields.push(Field::new("Data", hex(&bytes[2..])).at(2..bytes.len()));
+        return fields;
+    }
+
+    let common_info_length = bytes.get(2).copied().unwrap_or_default() as usize;
+    // The length counts its own octet, so Link Info never starts before the next one
+    let common_info_end = (2 + common_info_length.max(1)).min(bytes.len());
+    let mut common_info = vec![Field::new("Common Info Length", common_info_length).at(2..3)];
+    if let Some(mld_mac) = bytes.get(3..9) {
+        common_info.push(Field::new("MLD MAC Address", mac(mld_mac)).at(3..9));
+    }
+
+    let mut offset = 9;
+    for (present, length) in common_info_layout() {
+        if control & present == 0 {
+            continue;
+        }
+        let value = match bytes.get(offset..offset + length) {
+            Some(value) => value,
+            None => break,
+        };
+        let range = offset..offset + length;
+        common_info.push(match present {
+            LINK_ID_INFO_PRESENT => Field::new("Link ID", value[0] & 0x0f).at(range),
+            BSS_PARAMETERS_CHANGE_COUNT_PRESENT => {
+                Field::new("BSS Parameters Change Count", value[0]).at(range)
+            }
+            MEDIUM_SYNCHRONIZATION_DELAY_PRESENT => {
+                Field::new("Medium Synchronization Delay Information", hex(value)).at(range)
+            }
+            EML_CAPABILITIES_PRESENT => Field::new("EML Capabilities", hex(value)).at(range),
+            MLD_CAPABILITIES_PRESENT => Field::new(
+                "Maximum Number of Simultaneous Links",
+                (value[0] & 0x0f) + 1,
+            )
+            .at(range),
+            AP_MLD_ID_PRESENT => Field::new("AP MLD ID", value[0]).at(range),
+            _ => Field::new("Extended MLD Capabilities and Operations", hex(value)).at(range),
+        });
+        offset += length;
+    }
+
+    fields.push(
+        Field::new("Common Info", "")
+            .at(2..common_info_end)
+            .with_children(common_info),
+    );
+
+    // Link Info is a list of subelements, one Per-STA Profile per other affiliated AP
+    let mut offset = common_info_end;
+    while offset + 2 <= bytes.len() {
+        let (id, length) = (bytes[offset], bytes[offset + 1] as usize);
+        let end = (offset + 2 + length).min(bytes.len());
+        let body = &bytes[offset + 2..end];
+        fields.push(if id == PER_STA_PROFILE_SUBELEMENT_ID {
+            let mut children = Vec::new();
+            if let Some(control) = read_u16(body, 0) {
+                children.push(Field::new("Link ID", control & 0x0f).at(offset + 2..offset + 4));
+                children.push(
+                    Field::new("Complete Profile", control & 0x10 != 0).at(offset + 2..offset + 4),
+                );
+            }
+            // The STA Info that follows starts with its own length, then the link's MAC address
+            let sta_mac_present = read_u16(body, 0).unwrap_or_default() & 0x20 != 0;
+            if let Some(sta_mac) = body.get(3..9).filter(|_| sta_mac_present) {
+                children
+                    .push(Field::new("STA MAC Address", mac(sta_mac)).at(offset + 5..offset + 11));
+            }
+            Field::new("Per-STA Profile", hex(body))
+                .at(offset..end)
+                .with_children(children)
+        } else {
+            Field::new(&format!("Subelement {}", id), hex(body)).at(offset..end)
+        });
+        offset = end;
+    }
+
+    fields
+}
+
+// The optional Common Info fields in order, with their presence bit and length
+fn common_info_layout() -> [(u16, usize); 7] {
+    [
+        (LINK_ID_INFO_PRESENT, 1),
+        (BSS_PARAMETERS_CHANGE_COUNT_PRESENT, 1),
+        (MEDIUM_SYNCHRONIZATION_DELAY_PRESENT, 2),
+        (EML_CAPABILITIES_PRESENT, 2),
+        (MLD_CAPABILITIES_PRESENT, 2),
+        (AP_MLD_ID_PRESENT, 1),
+        (EXTENDED_MLD_CAPABILITIES_PRESENT, 2),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A Basic Multi-Link element with the Link ID Info present, and a complete Per-STA Profile
+    // with the STA MAC address of the other link
+    const MULTI_LINK: &[u8] = &[
+        0x10, 0x00, 8, 0x02, 0x11, 0x22, 0x33, 0x44, 0x55, 0x01, 0, 9, 0x32, 0x00, 7, 0x02, 0x11,
+        0x22, 0x33, 0x44, 0x66,
+    ];
+
+    #[test]
+    fn parses_common_info() {
+        let multi_link = BasicMultiLink::parse(MULTI_LINK).unwrap();
+        assert_eq!(multi_link.mld_mac, "02:11:22:33:44:55");
+        assert_eq!(multi_link.link_id, Some(1));
+
+        let fields = fields(MULTI_LINK);
+        assert_eq!(fields.len(), 3);
+        assert_eq!(fields[0].value, "Basic");
+        assert_eq!(fields[1].range, Some(2..10));
+        assert_eq!(fields[1].children[2].value, "1");
+        let profile = &fields[2];
+        assert_eq!(profile.range, Some(10..21));
+        assert_eq!(profile.children[0].value, "2");
+        assert_eq!(profile.children[1].value, "true");
+        assert_eq!(profile.children[2].value, "02:11:22:33:44:66");
+        assert_eq!(profile.children[2].range, Some(15..21));
+    }
+
+    #[test]
+    fn handles_truncated_elements() {
+        for end in 0..MULTI_LINK.len() {
+            let bytes = &MULTI_LINK[..end];
+            assert_eq!(BasicMultiLink::parse(bytes).is_some(), end >= 9);
+            let fields = fields(bytes);
+            assert!(!fields.is_empty());
+            assert!(fields
+                .iter()
+                .filter_map(|field| field.range.clone())
+                .all(|range| range.end <= end));
+        }
+    }
+
+    #[test]
+    fn handles_zero_lengths() {
+        // No Common Info and an empty Per-STA Profile
+        let bytes = [0x00, 0x00, 0, 0, 0];
+        assert!(BasicMultiLink::parse(&bytes).is_none());
+        let fields = fields(&bytes);
+        assert_eq!(fields[1].range, Some(2..3));
+        assert_eq!(fields[2].name, "Per-STA Profile");
+        assert_eq!(fields[2].range, Some(3..5));
+        assert!(fields[2].children.is_empty());
+    }
+}
@@ -0,0 +1,648 @@
+use crate::decode::{self, field::hex, read_u16, Field};
+use kawaiifi::Ie;
+
+pub const DS_PARAMETER_SET_ID: u8 = 3;
+pub const TIM_ID: u8 = 5;
+pub const HT_CAPABILITIES_ID: u8 = 45;
+pub const HT_OPERATION_ID: u8 = 61;
+pub const VHT_CAPABILITIES_ID: u8 = 191;
+pub const VHT_OPERATION_ID: u8 = 192;
+pub const HE_CAPABILITIES_EXT_ID: u8 = 35;
+pub const HE_OPERATION_EXT_ID: u8 = 36;
+
+const HT_CAPABILITY_INFORMATION: &[(u32, &str)] = &[
+    (0x0001, "LDPC Coding Capability"),
+    (0x0002, "40 MHz Channel Width"),
+    (0x0010, "HT-Greenfield"),
+    (0x0020, "Short GI for 20 MHz"),
+    (0x0040, "Short GI for 40 MHz"),
+    (0x0080, "Tx STBC"),
+    (0x0400, "HT-Delayed Block Ack"),
+    (0x0800, "7935 Octet A-MSDU"),
+    (0x1000, "DSSS/CCK Mode in 40 MHz"),
+    (0x4000, "40 MHz Intolerant"),
+    (0x8000, "L-SIG TXOP Protection Support"),
+];
+
+const VHT_CAPABILITIES_INFORMATION: &[(u32, &str)] = &[
+    (0x0000_0010, "Rx LDPC"),
+    (0x0000_0020, "Short GI for 80 MHz"),
+    (0x0000_0040, "Short GI for 160 and 80+80 MHz"),
+    (0x0000_0080, "Tx STBC"),
+    (0x0000_0800, "SU Beamformer Capable"),
+    (0x0000_1000, "SU Beamformee Capable"),
+    (0x0008_0000, "MU Beamformer Capable"),
+    (0x0010_0000, "MU Beamformee Capable"),
+    (0x0020_0000, "TXOP PS"),
+    (0x0040_0000, "+HTC-VHT Capable"),
+    (0x1000_0000, "Rx Antenna Pattern Consistency"),
+    (0x2000_0000, "Tx Antenna Pattern Consistency"),
+];
+
+// Each of these HE Operation Parameters adds an optional field to the end of the element
+const HE_VHT_OPERATION_INFORMATION_PRESENT: u32 = 0x00_4000;
+const HE_CO_HOSTED_BSS: u32 = 0x00_8000;
+const HE_6_GHZ_OPERATION_INFORMATION_PRESENT: u32 = 0x02_0000;
+
+const HE_OPERATION_PARAMETERS: &[(u32, &str)] = &[
+    (0x00_0008, "TWT Required"),
+    (
+        HE_VHT_OPERATION_INFORMATION_PRESENT,
+        "VHT Operation Information Present",
+    ),
+    (HE_CO_HOSTED_BSS, "Co-Hosted BSS"),
+    (0x01_0000, "ER SU Disable"),
+    (
+        HE_6_GHZ_OPERATION_INFORMATION_PRESENT,
+        "6 GHz Operation Information Present",
+    ),
+];
+
+const HE_MAC_CAPABILITIES: &[(usize, &str)] = &[
+    (0, "+HTC-HE Support"),
+    (1, "TWT Requester Support"),
+    (2, "TWT Responder Support"),
+    (19, "All Ack Support"),
+    (20, "TRS Support"),
+    (21, "BSR Support"),
+    (22, "Broadcast TWT Support"),
+    (23, "32-bit BA Bitmap Support"),
+    (24, "MU Cascading Support"),
+    (25, "Ack-Enabled Aggregation Support"),
+    (27, "OM Control Support"),
+    (28, "OFDMA RA Support"),
+    (31, "A-MSDU Fragmentation Support"),
+    (32, "Flexible TWT Schedule Support"),
+    (33, "Rx Control Frame to MultiBSS"),
+    (34, "BSRP BQRP A-MPDU Aggregation"),
+    (35, "QTP Support"),
+    (36, "BQR Support"),
+    (37, "PSR Responder"),
+    (38, "NDP Feedback Report Support"),
+    (39, "OPS Support"),
+    (40, "A-MSDU Not Under BA in Ack-Enabled A-MPDU Support"),
+    (42, "HE Subchannel Selective Transmission Support"),
+    (43, "UL 2x996-tone RU Support"),
+    (44, "OM Control UL MU Data Disable Rx Support"),
+    (45, "HE Dynamic SM Power Save"),
+    (46, "Punctured Sounding Support"),
+    (47, "HT and VHT Trigger Frame Rx Support"),
+];
+
+const HE_PHY_CAPABILITIES: &[(usize, &str)] = &[
+    (1, "40 MHz in 2.4 GHz"),
+    (2, "40 and 80 MHz in 5/6 GHz"),
+    (3, "160 MHz in 5/6 GHz"),
+    (4, "160/80+80 MHz in 5/6 GHz"),
+    (5, "242-tone RUs in 2.4 GHz"),
+    (6, "242-tone RUs in 5/6 GHz"),
+    (12, "Device Class A"),
+    (13, "LDPC Coding in Payload"),
+    (14, "HE SU PPDU with 1x HE-LTF and 0.8 us GI"),
+    (17, "NDP with 4x HE-LTF and 3.2 us GI"),
+    (18, "STBC Tx <= 80 MHz"),
+    (19, "STBC Rx <= 80 MHz"),
+    (20, "Doppler Tx"),
+    (21, "Doppler Rx"),
+    (22, "Full Bandwidth UL MU-MIMO"),
+    (23, "Partial Bandwidth UL MU-MIMO"),
+    (31, "SU Beamformer"),
+    (32, "SU Beamformee"),
+    (33, "MU Beamformer"),
+    (53, "Triggered SU Beamforming Feedback"),
+    (54, "Triggered MU Beamforming Partial BW Feedback"),
+    (55, "Triggered CQI Feedback"),
+    (56, "Partial Bandwidth Extended Range"),
+    (57, "Partial Bandwidth DL MU-MIMO"),
+    (58, "PPE Thresholds Present"),
+    (59, "PSR-based SR Support"),
+    (60, "Power Boost Factor Support"),
+    (61, "HE SU and HE MU PPDU with 4x HE-LTF and 0.8 us GI"),
+    (65, "20 MHz in 40 MHz HE PPDU in 2.4 GHz"),
+    (66, "20 MHz in 160/80+80 MHz HE PPDU"),
+    (67, "80 MHz in 160/80+80 MHz HE PPDU"),
+    (68, "HE ER SU PPDU with 1x HE-LTF and 0.8 us GI"),
+    (69, "Midamble Tx/Rx 2x and 1x HE-LTF"),
+    (72, "Longer Than 16 HE SIG-B OFDM Symbols Support"),
+    (73, "Non-Triggered CQI Feedback"),
+    (74, "Tx 1024-QAM Support < 242-tone RU"),
+    (75, "Rx 1024-QAM Support < 242-tone RU"),
+    (
+        76,
+        "Rx Full BW SU Using HE MU PPDU with Compressed HE-SIG-B",
+    ),
+    (
+        77,
+        "Rx Full BW SU Using HE MU PPDU with Non-Compressed HE-SIG-B",
+    ),
+];
+
+// Channel Width Set bits in the first octet of the HE PHY Capabilities
+const HE_CHANNEL_WIDTH_160: u8 = 0x08;
+const HE_CHANNEL_WIDTH_80_PLUS_80: u8 = 0x10;
+
+// The 20 MHz channels a 5 GHz BSS operates on, from the channel width and center frequency
+// segments of its VHT Operation (or the VHT Operation Information of its HE Operation) and the
+// secondary channel offset of its HT Operation
+pub fn operating_channels(ies: &[Ie], primary: u8) -> Vec<u8> {
+    let vht_operation = decode::find_ie(ies, VHT_OPERATION_ID).or_else(|| {
+        let he_operation = decode::find_ext_ie(ies, HE_OPERATION_EXT_ID)?;
+        let parameters = he_operation.get(0..3)?;
+        let parameters = u32::from_le_bytes([parameters[0], parameters[1], parameters[2], 0]);
+        if parameters & HE_VHT_OPERATION_INFORMATION_PRESENT != 0 {
+            he_operation.get(6..9)
+        } else {
+            None
+        }
+    });
+
+    if let Some(&[width, segment_0, segment_1, ..]) = vht_operation {
+        let distance = (segment_1 as i16 - segment_0 as i16).abs();
+        let segments = match (width, segment_1) {
+            (1, 0) => vec![(segment_0, 80)],
+            // 160 MHz is signalled with the second segment at its center
+            (1, _) if distance == 8 => vec![(segment_1, 160)],
+            (1, _) | (3, _) => vec![(segment_0, 80), (segment_1, 80)],
+            (2, _) => vec![(segment_0, 160)],
+            _ => Vec::new(),
+        };
+        if !segments.is_empty() {
+            return segments
+                .into_iter()
+                .flat_map(|(center, width_mhz)| segment_channels(center, width_mhz))
+                .collect();
+        }
+    }
+
+    let secondary_offset = decode::find_ie(ies, HT_OPERATION_ID)
+        .and_then(|bytes| bytes.get(1))
+        .map(|information| information & 0x03);
+    match secondary_offset {
+        Some(1) => vec![primary, primary.saturating_add(4)],
+        Some(3) => vec![primary.saturating_sub(4), primary],
+        _ => vec![primary],
+    }
+}
+
+// Channel numbers are 5 MHz apart, so a segment spans its center plus or minus half its width
+fn segment_channels(center: u8, width_mhz: u8) -> Vec<u8> {
+    let half = width_mhz / 10 - 2;
+    (center.saturating_sub(half)..=center.saturating_add(half))
+        .step_by(4)
+        .collect()
+}
+
+pub fn ds_parameter_set_fields(bytes: &[u8]) -> Vec<Field> {
+    bytes
+        .first()
+        .map(|channel| vec![Field::new("Current Channel", channel).at(0..1)])
+        .unwrap_or_default()
+}
+
+pub fn tim_fields(bytes: &[u8]) -> Vec<Field> {
+    let mut fields = Vec::new();
+    if let Some(count) = bytes.first() {
+        fields.push(Field::new("DTIM Count", count).at(0..1));
+    }
+    if let Some(period) = bytes.get(1) {
+        fields.push(Field::new("DTIM Period", period).at(1..2));
+    }
+    if let Some(control) = bytes.get(2) {
+        fields.push(
+            Field::new("Bitmap Control", format!("{:#04x}", control))
+                .at(2..3)
+                .with_children(vec![
+                    Field::new("Group Addressed Traffic", control & 0x01 != 0).at(2..3),
+                    Field::new("Bitmap Offset", (control >> 1) * 2).at(2..3),
+                ]),
+        );
+    }
+    if bytes.len() > 3 {
+        fields.push(Field::new("Partial Virtual Bitmap", hex(&bytes[3..])).at(3..bytes.len()));
+    }
+    fields
+}
+
+pub fn ht_capabilities_fields(bytes: &[u8]) -> Vec<Field> {
+    let mut fields = Vec::new();
+
+    let info = match read_u16(bytes, 0) {
+        Some(info) => info,
+        None => return fields,
+    };
+    let mut info_fields = Field::flags(info as u32, HT_CAPABILITY_INFORMATION, 0..2);
+    info_fields.push(
+        Field::new(
+            "SM Power Save",
+            match (info >> 2) & 0x03 {
+                0 => "Static",
+                1 => "Dynamic",
+                _ => "Disabled",
+            },
+        )
+        .at(0..1),
+    );
+    info_fields.push(Field::new("Rx STBC Streams", (info >> 8) & 0x03).at(1..2));
+    fields.push(
+        Field::new("HT Capability Information", format!("{:#06x}", info))
+            .at(0..2)
+            .with_children(info_fields),
+    );
+
+    if let Some(parameters) = bytes.get(2) {
+        fields.push(
+            Field::new("A-MPDU Parameters", format!("{:#04x}", parameters))
+                .at(2..3)
+                .with_children(vec![
+                    Field::new(
+                        "Maximum A-MPDU Length",
+                        format!("{} octets", (1u32 << (13 + (parameters & 0x03))) - 1),
+                    )
+                    .at(2..3),
+                    Field::new(
+                        "Minimum MPDU Start Spacing",
+                        match (parameters >> 2) & 0x07 {
+                            0 => "No restriction".to_string(),
+                            spacing => format!("{} us", 2f64.powi(spacing as i32 - 3)),
+                        },
+                    )
+                    .at(2..3),
+                ]),
+        );
+    }
+
+    if let Some(mcs_set) = bytes.get(3..19) {
+        let spatial_streams = mcs_set[0..4].iter().filter(|mcs| **mcs != 0).count();
+        let highest_rate = read_u16(mcs_set, 10).unwrap_or_default() & 0x03ff;
+        fields.push(
+            Field::new(
+                "Supported MCS Set",
+                format!("{} spatial streams", spatial_streams),
+            )
+            .at(3..19)
+            .with_children(vec![
+                Field::new("Rx MCS Bitmask", hex(&mcs_set[0..10])).at(3..13),
+                Field::new(
+                    "Rx Highest Supported Data Rate",
+                    format!("{} Mbps", highest_rate),
+                )
+                .at(13..15),
+                Field::new("Tx MCS Set Defined", mcs_set[12] & 0x01 != 0).at(15..16),
+            ]),
+        );
+    }
+
+    for (range, name) in &[
+        (19..21, "HT Extended Capabilities"),
+        (21..25, "Transmit Beamforming Capabilities"),
+        (25..26, "ASEL Capabilities"),
+    ] {
+        if let Some(value) = bytes.get(range.clone()) {
+            fields.push(Field::new(name, hex(value)).at(range.clone()));
+        }
+    }
+
+    fields
+}
+
+pub fn ht_operation_fields(bytes: &[u8]) -> Vec<Field> {
+    let mut fields = Vec::new();
+    match bytes.first() {
+        Some(channel) => fields.push(Field::new("Primary Channel", channel).at(0..1)),
+        None => return fields,
+    }
+
+    if let Some(info) = bytes.get(1..6) {
+        let operation_mode = u16::from_le_bytes([info[1], info[2]]);
+        fields.push(
+            Field::new("HT Operation Information", hex(info))
+                .at(1..6)
+                .with_children(vec![
+                    Field::new(
+                        "Secondary Channel Offset",
+                        match info[0] & 0x03 {
+                            1 => "Above",
+                            3 => "Below",
+                            _ => "None",
+                        },
+                    )
+                    .at(1..2),
+                    Field::new(
+                        "STA Channel Width",
+                        if info[0] & 0x04 != 0 { "Any" } else { "20 MHz" },
+                    )
+                    .at(1..2),
+                    Field::new("RIFS Mode", info[0] & 0x08 != 0).at(1..2),
+                    Field::new(
+                        "HT Protection",
+                        match operation_mode & 0x03 {
+                            0 => "No protection",
+                            1 => "Nonmember protection",
+                            2 => "20 MHz protection",
+                            _ => "Non-HT mixed",
+                        },
+                    )
+                    .at(2..3),
+                    Field::new("Nongreenfield HT STAs Present", operation_mode & 0x04 != 0)
+                        .at(2..3),
+                    Field::new("OBSS Non-HT STAs Present", operation_mode & 0x10 != 0).at(2..3),
+                    Field::new(
+                        "Channel Center Frequency Segment 2",
+                        (operation_mode >> 5) & 0xff,
+                    )
+                    .at(2..4),
+                    Field::new("Dual Beacon", info[3] & 0x40 != 0).at(4..5),
+                    Field::new("Dual CTS Protection", info[3] & 0x80 != 0).at(4..5),
+                    Field::new("STBC Beacon", info[4] & 0x01 != 0).at(5..6),
+                ]),
+        );
+    }
+
+    if let Some(mcs_set) = bytes.get(6..22) {
+        fields.push(Field::new("Basic HT-MCS Set", hex(mcs_set)).at(6..22));
+    }
+
+    fields
+}
+
+pub fn vht_capabilities_fields(bytes: &[u8]) -> Vec<Field> {
+    let mut fields = Vec::new();
+
+    let info = match bytes.get(0..4) {
+        Some(info) => u32::from_le_bytes([info[0], info[1], info[2], info[3]]),
+        None => return fields,
+    };
+    let mut info_fields = vec![
+        Field::new(
+            "Maximum MPDU Length",
+            match info & 0x03 {
+                0 => "3895 octets",
+                1 => "7991 octets",
+                _ => "11454 octets",
+            },
+        )
+        .at(0..1),
+        Field::new(
+            "Supported Channel Width Set",
+            match (info >> 2) & 0x03 {
+                0 => "80 MHz",
+                1 => "160 MHz",
+                _ => "160 and 80+80 MHz",
+            },
+        )
+        .at(0..1),
+        Field::new("Rx STBC Streams", (info >> 8) & 0x07).at(1..2),
+        Field::new(
+            "Maximum A-MPDU Length",
+            format!("{} octets", (1u32 << (13 + ((info >> 23) & 0x07))) - 1),
+        )
+        .at(2..4),
+    ];
+    info_fields.extend(Field::flags(info, VHT_CAPABILITIES_INFORMATION, 0..4));
+    fields.push(
+        Field::new("VHT Capabilities Information", format!("{:#010x}", info))
+            .at(0..4)
+            .with_children(info_fields),
+    );
+
+    if let (Some(rx_map), Some(rx_highest), Some(tx_map), Some(tx_highest)) = (
+        read_u16(bytes, 4),
+        read_u16(bytes, 6),
+        read_u16(bytes, 8),
+        read_u16(bytes, 10),
+    ) {
+        fields.push(
+            Field::new("Supported VHT-MCS and NSS Set", hex(&bytes[4..12]))
+                .at(4..12)
+                .with_children(vec![
+                    Field::new("Rx VHT-MCS Map", mcs_map(rx_map, VHT_MCS)).at(4..6),
+                    Field::new(
+                        "Rx Highest Supported Long GI Data Rate",
+                        format!("{} Mbps", rx_highest & 0x1fff),
+                    )
+                    .at(6..8),
+                    Field::new("Tx VHT-MCS Map", mcs_map(tx_map, VHT_MCS)).at(8..10),
+                    Field::new(
+                        "Tx Highest Supported Long GI Data Rate",
+                        format!("{} Mbps", tx_highest & 0x1fff),
+                    )
+                    .at(10..12),
+                ]),
+        );
+    }
+
+    fields
+}
+
+pub fn vht_operation_fields(bytes: &[u8]) -> Vec<Field> {
+    let mut fields = vht_operation_information_fields(bytes);
+    if let Some(basic_map) = read_u16(bytes, 3) {
+        fields.push(Field::new("Basic VHT-MCS and NSS Set", mcs_map(basic_map, VHT_MCS)).at(3..5));
+    }
+    fields
+}
+
+// Channel Width and the two Channel Center Frequency Segments, which HE Operation repeats
+fn vht_operation_information_fields(bytes: &[u8]) -> Vec<Field> {
+    let mut fields = Vec::new();
+    if let Some(width) = bytes.first() {
+        fields.push(
+            Field::new(
+                "Channel Width",
+                match width {
+                    0 => "20 or 40 MHz",
+                    1 => "80, 160 or 80+80 MHz",
+                    2 => "160 MHz (deprecated)",
+                    3 => "80+80 MHz (deprecated)",
+                    _ => "Reserved",
+                },
+            )
+            .at(0..1),
+        );
+    }
+    if let Some(segment) = bytes.get(1) {
+        fields.push(Field::new("Channel Center Frequency Segment 0", segment).at(1..2));
+    }
+    if let Some(segment) = bytes.get(2) {
+        fields.push(Field::new("Channel Center Frequency Segment 1", segment).at(2..3));
+    }
+    fields
+}
+
+// The fields of an HE Capabilities element after its extension ID
+pub fn he_capabilities_fields(bytes: &[u8]) -> Vec<Field> {
+    let mut fields = Vec::new();
+    match bytes.get(0..6) {
+        Some(mac) => fields.push(
+            Field::new("HE MAC Capabilities Information", hex(mac))
+                .at(0..6)
+                .with_children(Field::bitmap(mac, HE_MAC_CAPABILITIES, 0)),
+        ),
+        None => return fields,
+    }
+    let phy = match bytes.get(6..17) {
+        Some(phy) => phy,
+        None => return fields,
+    };
+    fields.push(
+        Field::new("HE PHY Capabilities Information", hex(phy))
+            .at(6..17)
+            .with_children(Field::bitmap(phy, HE_PHY_CAPABILITIES, 6)),
+    );
+
+    // The 160 and 80+80 MHz maps are only present if the PHY capabilities support those widths
+    let mut offset = 17;
+    let mut widths = vec!["<= 80 MHz"];
+    if phy[0] & HE_CHANNEL_WIDTH_160 != 0 {
+        widths.push("160 MHz");
+    }
+    if phy[0] & HE_CHANNEL_WIDTH_80_PLUS_80 != 0 {
+        widths.push("80+80 MHz");
+    }
+    for width in widths {
+        let (rx_map, tx_map) = match (read_u16(bytes, offset), read_u16(bytes, offset + 2)) {
+            (Some(rx_map), Some(tx_map)) => (rx_map, tx_map),
+            _ => return fields,
+        };
+        fields.push(
+            Field::new(
+                &format!("Supported HE-MCS and NSS Set ({})", width),
+                hex(&bytes[offset..offset + 4]),
+            )
+            .at(offset..offset + 4)
+            .with_children(vec![
+                Field::new("Rx HE-MCS Map", mcs_map(rx_map, HE_MCS)).at(offset..offset + 2),
+                Field::new("Tx HE-MCS Map", mcs_map(tx_map, HE_MCS)).at(offset + 2..offset + 4),
+            ]),
+        );
+        offset += 4;
+    }
+
+    if offset < bytes.len() {
+        fields.push(Field::new("PPE Thresholds", hex(&bytes[offset..])).at(offset..bytes.len()));
+    }
+
+    fields
+}
+
+// The fields of an HE Operation element after its extension ID
+pub fn he_operation_fields(bytes: &[u8]) -> Vec<Field> {
+    let mut fields = Vec::new();
+    let parameters = match bytes.get(0..3) {
+        Some(parameters) => u32::from_le_bytes([parameters[0], parameters[1], parameters[2], 0]),
+        None => return fields,
+    };
+    let mut parameter_fields = vec![
+        Field::new(
+            "Default PE Duration",
+            format!("{} us", (parameters & 0x07) * 4),
+        )
+        .at(0..1),
+        Field::new(
+            "TXOP Duration RTS Threshold",
+            match (parameters >> 4) & 0x03ff {
+                1023 => "Disabled".to_string(),
+                threshold => format!("{} us", threshold * 32),
+            },
+        )
+        .at(0..2),
+    ];
+    parameter_fields.extend(Field::flags(parameters, HE_OPERATION_PARAMETERS, 0..3));
+    fields.push(
+        Field::new("HE Operation Parameters", format!("{:#08x}", parameters))
+            .at(0..3)
+            .with_children(parameter_fields),
+    );
+
+    let color = match bytes.get(3) {
+        Some(color) => *color,
+        None => return fields,
+    };
+    fields.push(
+        Field::new("BSS Color Information", format!("{:#04x}", color))
+            .at(3..4)
+            .with_children(vec![
+                Field::new("BSS Color", color & 0x3f).at(3..4),
+                Field::new("Partial BSS Color", color & 0x40 != 0).at(3..4),
+                Field::new("BSS Color Disabled", color & 0x80 != 0).at(3..4),
+            ]),
+    );
+
+    match read_u16(bytes, 4) {
+        Some(basic_map) => {
+            fields.push(Field::new("Basic HE-MCS and NSS Set", mcs_map(basic_map, HE_MCS)).at(4..6))
+        }
+        None => return fields,
+    }
+
+    let mut offset = 6;
+    if parameters & HE_VHT_OPERATION_INFORMATION_PRESENT != 0 {
+        if let Some(info) = bytes.get(offset..offset + 3) {
+            fields.push(
+                Field::new("VHT Operation Information", hex(info))
+                    .at(offset..offset + 3)
+                    .with_children(
+                        vht_operation_information_fields(info)
+                            .into_iter()
+                            .map(|field| field.offset(offset))
+                            .collect(),
+                    ),
+            );
+        }
+        offset += 3;
+    }
+    if parameters & HE_CO_HOSTED_BSS != 0 {
+        if let Some(indicator) = bytes.get(offset) {
+            fields.push(
+                Field::new("Max Co-Hosted BSSID Indicator", indicator).at(offset..offset + 1),
+            );
+        }
+        offset += 1;
+    }
+    if parameters & HE_6_GHZ_OPERATION_INFORMATION_PRESENT != 0 {
+        if let Some(info) = bytes.get(offset..offset + 5) {
+            let at = |i: usize| offset + i..offset + i + 1;
+            fields.push(
+                Field::new("6 GHz Operation Information", hex(info))
+                    .at(offset..offset + 5)
+                    .with_children(vec![
+                        Field::new("Primary Channel", info[0]).at(at(0)),
+                        Field::new(
+                            "Channel Width",
+                            match info[1] & 0x03 {
+                                0 => "20 MHz",
+                                1 => "40 MHz",
+                                2 => "80 MHz",
+                                _ => "160 or 80+80 MHz",
+                            },
+                        )
+                        .at(at(1)),
+                        Field::new("Duplicate Beacon", info[1] & 0x04 != 0).at(at(1)),
+                        Field::new("Channel Center Frequency Segment 0", info[2]).at(at(2)),
+                        Field::new("Channel Center Frequency Segment 1", info[3]).at(at(3)),
+                        Field::new("Minimum Rate", format!("{} Mbps", info[4])).at(at(4)),
+                    ]),
+            );
+        }
+    }
+
+    fields
+}
+
+// The highest MCS each value of a 2 bit Max MCS For n SS subfield allows
+const VHT_MCS: [&str; 3] = ["0-7", "0-8", "0-9"];
+const HE_MCS: [&str; 3] = ["0-7", "0-9", "0-11"];
+
+fn mcs_map(map: u16, mcs: [&str; 3]) -> String {
+    let streams = (0..8)
+        .filter_map(|stream| {
+            mcs.get(((map >> (stream * 2)) & 0x03) as usize)
+                .map(|mcs| format!("{}SS MCS {}", stream + 1, mcs))
+        })
+        .collect::<Vec<String>>();
+    if streams.is_empty() {
+        "Not supported".to_string()
+    } else {
+        streams.join(", ")
+    }
+}
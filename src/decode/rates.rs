@@ -0,0 +1,89 @@
+use crate::decode::{self, Field};
+use kawaiifi::Ie;
+
+pub const SUPPORTED_RATES_ID: u8 = 1;
+pub const EXTENDED_SUPPORTED_RATES_ID: u8 = 50;
+
+// Values from 121 up are BSS membership selectors (e.g. HT PHY, SAE H2E only) rather than rates
+const FIRST_MEMBERSHIP_SELECTOR: u8 = 121;
+
+const DSSS_RATES: &[u8] = &[2, 4, 11, 22];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Rate {
+    // In units of 500 kbps
+    pub value: u8,
+    pub basic: bool,
+}
+
+impl Rate {
+    // 1, 2, 5.5 and 11 Mbps are the 802.11b DSSS/CCK rates
+    pub fn is_dsss(&self) -> bool {
+        DSSS_RATES.contains(&self.value)
+    }
+
+    pub fn mbps(&self) -> f64 {
+        self.value as f64 / 2.0
+    }
+}
+
+// The rates from the Supported Rates and Extended Supported Rates elements
+pub fn supported_rates(ies: &[Ie]) -> Vec<Rate> {
+    [SUPPORTED_RATES_ID, EXTENDED_SUPPORTED_RATES_ID]
+        .iter()
+        .filter_map(|id| decode::find_ie(ies, *id))
+        .flatten()
+        .map(|byte| Rate {
+            value: byte & 0x7f,
+            basic: byte & 0x80 != 0,
+        })
+        .filter(|rate| rate.value < FIRST_MEMBERSHIP_SELECTOR)
+        .collect()
+}
+
+// The lowest rate every station in the BSS must support, which management frames are sent at
+pub fn min_basic_rate(ies: &[Ie]) -> Option<Rate> {
+    supported_rates(ies)
+        .into_iter()
+        .filter(|rate| rate.basic)
+        .min_by_key(|rate| rate.value)
+}
+
+// The fields of a Supported Rates or Extended Supported Rates element
+pub fn fields(bytes: &[u8]) -> Vec<Field> {
+    bytes
+        .iter()
+        .enumerate()
+        .map(|(i, byte)| {
+            let value = byte & 0x7f;
+            let basic = byte & 0x80 != 0;
+            let field = if value >= FIRST_MEMBERSHIP_SELECTOR && basic {
+                Field::new("BSS Membership Selector", membership_selector_name(value))
+            } else {
+                let rate = Rate { value, basic };
+                Field::new(
+                    "Rate",
+                    format!(
+                        "{} Mbps{}",
+                        rate.mbps(),
+                        if basic { " (basic)" } else { "" }
+                    ),
+                )
+            };
+            field.at(i..i + 1)
+        })
+        .collect()
+}
+
+fn membership_selector_name(value: u8) -> String {
+    match value {
+        121 => "EHT PHY".to_string(),
+        122 => "HE PHY".to_string(),
+        123 => "SAE Hash-to-Element Only".to_string(),
+        124 => "EPD".to_string(),
+        125 => "GLK".to_string(),
+        126 => "VHT PHY".to_string(),
+        127 => "HT PHY".to_string(),
+        other => other.to_string(),
+    }
+}
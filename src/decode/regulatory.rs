@@ -0,0 +1,210 @@
+use crate::{
+    analysis::FrequencyBand,
+    decode::{self, phy, ssid, Field},
+};
+use kawaiifi::Ie;
+
+pub const COUNTRY_ID: u8 = 7;
+pub const POWER_CONSTRAINT_ID: u8 = 32;
+pub const TPC_REPORT_ID: u8 = 35;
+
+// Country IE triplets with a first octet from 201 up are operating extension triplets
+const FIRST_OPERATING_EXTENSION_ID: u8 = 201;
+
+// 5 GHz channels that require radar detection (U-NII-2A and U-NII-2C)
+const DFS_CHANNELS: &[(u8, u8)] = &[(52, 64), (100, 144)];
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Country {
+    pub code: String,
+    pub environment: u8,
+    pub subbands: Vec<Subband>,
+    pub operating_classes: Vec<OperatingClass>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Subband {
+    pub first_channel: u8,
+    pub channel_count: u8,
+    pub max_tx_power_dbm: i8,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct OperatingClass {
+    pub operating_class: u8,
+    pub coverage_class: u8,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct TpcReport {
+    pub tx_power_dbm: i8,
+    pub link_margin_db: i8,
+}
+
+impl Country {
+    pub fn from_ies(ies: &[Ie]) -> Option<Country> {
+        decode::find_ie(ies, COUNTRY_ID).and_then(Country::parse)
+    }
+
+    pub fn parse(bytes: &[u8]) -> Option<Country> {
+        let mut country = Country {
+            code: ssid::escape(bytes.get(0..2)?, false),
+            environment: *bytes.get(2)?,
+            subbands: Vec::new(),
+            operating_classes: Vec::new(),
+        };
+
+        for triplet in bytes[3..].chunks_exact(3) {
+            if triplet[0] < FIRST_OPERATING_EXTENSION_ID {
+                country.subbands.push(Subband {
+                    first_channel: triplet[0],
+                    channel_count: triplet[1],
+                    max_tx_power_dbm: triplet[2] as i8,
+                });
+            } else {
+                country.operating_classes.push(OperatingClass {
+                    operating_class: triplet[1],
+                    coverage_class: triplet[2],
+                });
+            }
+        }
+
+        Some(country)
+    }
+
+    pub fn environment_name(&self) -> String {
+        environment_name(self.environment)
+    }
+
+    // The subband that lists the channel, if any
+    pub fn subband(&self, channel: u8, band: FrequencyBand) -> Option<&Subband> {
+        self.subbands
+            .iter()
+            .find(|subband| subband.channels(band).contains(&(channel as u16)))
+    }
+}
+
+impl Subband {
+    // Channels are numbered 1 apart in 2.4 GHz and 4 apart (20 MHz) elsewhere
+    pub fn channels(&self, band: FrequencyBand) -> std::ops::RangeInclusive<u16> {
+        let spacing = if band == FrequencyBand::TwoPointFourGhz {
+            1
+        } else {
+            4
+        };
+        let first = self.first_channel as u16;
+        first..=first + (self.channel_count.max(1) as u16 - 1) * spacing
+    }
+}
+
+// The number of dB the local maximum transmit power is below the regulatory maximum
+pub fn power_constraint_db(ies: &[Ie]) -> Option<u8> {
+    decode::find_ie(ies, POWER_CONSTRAINT_ID).and_then(|bytes| bytes.first().copied())
+}
+
+impl TpcReport {
+    pub fn from_ies(ies: &[Ie]) -> Option<TpcReport> {
+        let bytes = decode::find_ie(ies, TPC_REPORT_ID)?;
+        Some(TpcReport {
+            tx_power_dbm: *bytes.first()? as i8,
+            link_margin_db: *bytes.get(1)? as i8,
+        })
+    }
+}
+
+fn environment_name(environment: u8) -> String {
+    match environment {
+        b' ' => "Any".to_string(),
+        b'O' => "Outdoor".to_string(),
+        b'I' => "Indoor".to_string(),
+        b'X' => "Noncountry entity".to_string(),
+        0x04 => "Global operating classes".to_string(),
+        other => format!("{:#04x}", other),
+    }
+}
+
+pub fn country_fields(bytes: &[u8]) -> Vec<Field> {
+    let mut fields = Vec::new();
+    match bytes.get(0..2) {
+        Some(code) => fields.push(Field::new("Country", ssid::escape(code, false)).at(0..2)),
+        None => return fields,
+    }
+    match bytes.get(2) {
+        Some(environment) => {
+            fields.push(Field::new("Environment", environment_name(*environment)).at(2..3))
+        }
+        None => return fields,
+    }
+
+    for (i, triplet) in bytes[3..].chunks_exact(3).enumerate() {
+        let at = 3 + i * 3;
+        fields.push(if triplet[0] < FIRST_OPERATING_EXTENSION_ID {
+            Field::new(
+                "Subband",
+                format!(
+                    "{} channels from {}, max {} dBm",
+                    triplet[1], triplet[0], triplet[2] as i8
+                ),
+            )
+            .at(at..at + 3)
+            .with_children(vec![
+                Field::new("First Channel", triplet[0]).at(at..at + 1),
+                Field::new("Number of Channels", triplet[1]).at(at + 1..at + 2),
+                Field::new(
+                    "Maximum Transmit Power",
+                    format!("{} dBm", triplet[2] as i8),
+                )
+                .at(at + 2..at + 3),
+            ])
+        } else {
+            Field::new(
+                "Operating Extension",
+                format!("Operating class {}", triplet[1]),
+            )
+            .at(at..at + 3)
+            .with_children(vec![
+                Field::new("Operating Extension Identifier", triplet[0]).at(at..at + 1),
+                Field::new("Operating Class", triplet[1]).at(at + 1..at + 2),
+                Field::new("Coverage Class", triplet[2]).at(at + 2..at + 3),
+            ])
+        });
+    }
+
+    fields
+}
+
+pub fn power_constraint_fields(bytes: &[u8]) -> Vec<Field> {
+    bytes
+        .first()
+        .map(|constraint| {
+            vec![Field::new("Local Power Constraint", format!("{} dB", constraint)).at(0..1)]
+        })
+        .unwrap_or_default()
+}
+
+pub fn tpc_report_fields(bytes: &[u8]) -> Vec<Field> {
+    let mut fields = Vec::new();
+    if let Some(tx_power) = bytes.first() {
+        fields.push(Field::new("Transmit Power", format!("{} dBm", *tx_power as i8)).at(0..1));
+    }
+    if let Some(link_margin) = bytes.get(1) {
+        fields.push(Field::new("Link Margin", format!("{} dB", *link_margin as i8)).at(1..2));
+    }
+    fields
+}
+
+pub fn is_dfs_channel(channel: u8, band: FrequencyBand) -> bool {
+    band == FrequencyBand::FiveGhz
+        && DFS_CHANNELS
+            .iter()
+            .any(|(first, last)| channel >= *first && channel <= *last)
+}
+
+// Whether any of the channels a BSS operates on, not only its primary channel, needs radar
+// detection
+pub fn is_dfs_bss(ies: &[Ie], primary: u8, band: FrequencyBand) -> bool {
+    band == FrequencyBand::FiveGhz
+        && phy::operating_channels(ies, primary)
+            .into_iter()
+            .any(|channel| is_dfs_channel(channel, band))
+}
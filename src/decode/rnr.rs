@@ -0,0 +1,357 @@
+use crate::decode::{
+    self,
+    field::{hex, mac},
+    Field,
+};
+use kawaiifi::{Ie, InformationElement};
+
+pub const REDUCED_NEIGHBOR_REPORT_ID: u8 = 201;
+
+// Where each part of a TBTT Information field starts, which depends on the field's length.
+// Fields longer than 16 octets are extensions of the 16 octet layout.
+struct TbttLayout {
+    bssid: Option<usize>,
+    short_ssid: Option<usize>,
+    bss_parameters: Option<usize>,
+    psd: Option<usize>,
+    mld_parameters: Option<usize>,
+}
+
+pub const BSS_PARAMETERS_SAME_SSID: u8 = 0x02;
+pub const BSS_PARAMETERS_COLOCATED_AP: u8 = 0x40;
+
+const BSS_PARAMETERS: &[(u32, &str)] = &[
+    (0x01, "OCT Recommended"),
+    (0x02, "Same SSID"),
+    (0x04, "Multiple BSSID"),
+    (0x08, "Transmitted BSSID"),
+    (0x10, "Member of ESS with 2.4/5 GHz Co-Located AP"),
+    (0x20, "Unsolicited Probe Responses Active"),
+    (0x40, "Co-Located AP"),
+];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct MldParameters {
+    // 0 when the reported AP is affiliated with the same AP MLD as the reporting AP
+    pub ap_mld_id: u8,
+    pub link_id: u8,
+    pub change_count: u8,
+    pub disabled_link: bool,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct NeighborAp {
+    pub operating_class: u8,
+    pub channel: u8,
+    pub bssid: Option<String>,
+    pub short_ssid: Option<u32>,
+    pub bss_parameters: Option<u8>,
+    pub mld_parameters: Option<MldParameters>,
+}
+
+impl NeighborAp {
+    // Every Reduced Neighbor Report element of a BSS, flattened to one entry per reported AP
+    pub fn from_ies(ies: &[Ie]) -> Vec<NeighborAp> {
+        ies.iter()
+            .filter(|ie| ie.id() == REDUCED_NEIGHBOR_REPORT_ID)
+            .flat_map(|ie| parse(ie.bytes()))
+            .collect()
+    }
+
+    pub fn is_same_ssid(&self) -> bool {
+        self.bss_parameters.unwrap_or_default() & BSS_PARAMETERS_SAME_SSID != 0
+    }
+
+    pub fn is_colocated(&self) -> bool {
+        self.bss_parameters.unwrap_or_default() & BSS_PARAMETERS_COLOCATED_AP != 0
+    }
+}
+
+// The short SSID is the CRC-32 of the SSID, as used by Ethernet
+pub fn short_ssid(ssid: &[u8]) -> u32 {
+    !ssid.iter().fold(!0u32, |crc, byte| {
+        (0..8).fold(crc ^ *byte as u32, |crc, _| {
+            if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
+
+pub fn parse(bytes: &[u8]) -> Vec<NeighborAp> {
+    let mut neighbors = Vec::new();
+    let mut offset = 0;
+
+    while let Some(header) = decode::read_u16(bytes, offset) {
+        let count = ((header >> 4) & 0x0f) as usize + 1;
+        let length = (header >> 8) as usize;
+        let (operating_class, channel) = match (bytes.get(offset + 2), bytes.get(offset + 3)) {
+            (Some(operating_class), Some(channel)) => (*operating_class, *channel),
+            _ => break,
+        };
+
+        // A TBTT Information Length of 0 leaves nothing to report about the neighbors
+        let layout = tbtt_layout(length);
+        neighbors.extend(
+            (0..count)
+                .filter(|_| length > 0)
+                .filter_map(|i| bytes.get(offset + 4 + i * length..offset + 4 + (i + 1) * length))
+                .map(|info| NeighborAp {
+                    operating_class,
+                    channel,
+                    bssid: layout.bssid.and_then(|at| info.get(at..at + 6)).map(mac),
+                    short_ssid: layout
+                        .short_ssid
+                        .and_then(|at| info.get(at..at + 4))
+                        .map(|ssid| u32::from_le_bytes([ssid[0], ssid[1], ssid[2], ssid[3]])),
+                    bss_parameters: layout.bss_parameters.and_then(|at| info.get(at).copied()),
+                    mld_parameters: layout
+                        .mld_parameters
+                        .and_then(|at| info.get(at..at + 3))
+                        .map(mld_parameters),
+                }),
+        );
+        offset += 4 + count * length;
+    }
+
+    neighbors
+}
+
+pub fn fields(bytes: &[u8]) -> Vec<Field> {
+    let mut fields = Vec::new();
+    let mut offset = 0;
+
+    while let Some(header) = decode::read_u16(bytes, offset) {
+        let count = ((header >> 4) & 0x0f) as usize + 1;
+        let length = (header >> 8) as usize;
+        let end = (offset + 4 + count * length).min(bytes.len());
+        let (operating_class, channel) = match (bytes.get(offset + 2), bytes.get(offset + 3)) {
+            (Some(operating_class), Some(channel)) => (*operating_class, *channel),
+            _ => break,
+        };
+
+        let mut children = vec![
+            Field::new("TBTT Information Count", count).at(offset..offset + 2),
+            Field::new("TBTT Information Length", length).at(offset..offset + 2),
+            Field::new("Operating Class", operating_class).at(offset + 2..offset + 3),
+            Field::new("Channel", channel).at(offset + 3..offset + 4),
+        ];
+
+        let layout = tbtt_layout(length);
+        for i in (0..count).filter(|_| length > 0) {
+            let start = offset + 4 + i * length;
+            let info = match bytes.get(start..start + length) {
+                Some(info) => info,
+                None => break,
+            };
+            children.push(
+                Field::new("TBTT Information", hex(info))
+                    .at(start..start + length)
+                    .with_children(tbtt_fields(info, &layout, start)),
+            );
+        }
+
+        fields.push(
+            Field::new(
+                "Neighbor AP Information",
+                format!("Operating class {}, channel {}", operating_class, channel),
+            )
+            .at(offset..end)
+            .with_children(children),
+        );
+
+        offset = end;
+        if offset >= bytes.len() {
+            break;
+        }
+    }
+
+    fields
+}
+
+fn tbtt_layout(length: usize) -> TbttLayout {
+    let (bssid, short_ssid, bss_parameters, psd, mld_parameters) = match length {
+        2 => (None, None, Some(1), None, None),
+        5 => (None, Some(1), None, None, None),
+        6 => (None, Some(1), Some(5), None, None),
+        7 => (Some(1), None, None, None, None),
+        8 => (Some(1), None, Some(7), None, None),
+        9 => (Some(1), None, Some(7), Some(8), None),
+        11 => (Some(1), Some(7), None, None, None),
+        12 => (Some(1), Some(7), Some(11), None, None),
+        13..=15 => (Some(1), Some(7), Some(11), Some(12), None),
+        length if length >= 16 => (Some(1), Some(7), Some(11), Some(12), Some(13)),
+        _ => (None, None, None, None, None),
+    };
+
+    TbttLayout {
+        bssid,
+        short_ssid,
+        bss_parameters,
+        psd,
+        mld_parameters,
+    }
+}
+
+fn tbtt_fields(info: &[u8], layout: &TbttLayout, start: usize) -> Vec<Field> {
+    let mut fields = Vec::new();
+    if let Some(tbtt_offset) = info.first() {
+        fields.push(Field::new("Neighbor AP TBTT Offset", tbtt_offset).at(start..start + 1));
+    }
+
+    if let Some(bssid) = layout.bssid.and_then(|at| info.get(at..at + 6)) {
+        let at = start + layout.bssid.unwrap_or_default();
+        fields.push(Field::new("BSSID", mac(bssid)).at(at..at + 6));
+    }
+    if let Some(short_ssid) = layout.short_ssid.and_then(|at| info.get(at..at + 4)) {
+        let at = start + layout.short_ssid.unwrap_or_default();
+        fields.push(
+            Field::new(
+                "Short SSID",
+                format!(
+                    "{:08x}",
+                    u32::from_le_bytes([
+                        short_ssid[0],
+                        short_ssid[1],
+                        short_ssid[2],
+                        short_ssid[3]
+                    ])
+                ),
+            )
+            .at(at..at + 4),
+        );
+    }
+    if let Some(&bss_parameters) = layout.bss_parameters.and_then(|at| info.get(at)) {
+        let at = start + layout.bss_parameters.unwrap_or_default();
+        fields.push(
+            Field::new("BSS Parameters", format!("{:#04x}", bss_parameters))
+                .at(at..at + 1)
+                .with_children(Field::flags(
+                    bss_parameters as u32,
+                    BSS_PARAMETERS,
+                    at..at + 1,
+                )),
+        );
+    }
+    if let Some(&psd) = layout.psd.and_then(|at| info.get(at)) {
+        let at = start + layout.psd.unwrap_or_default();
+        fields.push(
+            Field::new(
+                "20 MHz PSD",
+                format!("{:.1} dBm/MHz", psd as i8 as f64 / 2.0),
+            )
+            .at(at..at + 1),
+        );
+    }
+    if let Some(bytes) = layout.mld_parameters.and_then(|at| info.get(at..at + 3)) {
+        let at = start + layout.mld_parameters.unwrap_or_default();
+        let parameters = mld_parameters(bytes);
+        fields.push(
+            Field::new(
+                "MLD Parameters",
+                format!(
+                    "AP MLD ID {}, link {}",
+                    parameters.ap_mld_id, parameters.link_id
+                ),
+            )
+            .at(at..at + 3)
+            .with_children(vec![
+                Field::new("AP MLD ID", parameters.ap_mld_id).at(at..at + 1),
+                Field::new("Link ID", parameters.link_id).at(at + 1..at + 2),
+                Field::new("BSS Parameters Change Count", parameters.change_count)
+                    .at(at + 1..at + 3),
+                Field::new("Disabled Link", parameters.disabled_link).at(at + 2..at + 3),
+            ]),
+        );
+    }
+
+    fields
+}
+
+fn mld_parameters(bytes: &[u8]) -> MldParameters {
+    MldParameters {
+        ap_mld_id: bytes[0],
+        link_id: bytes[1] & 0x0f,
+        change_count: (bytes[1] >> 4) | ((bytes[2] & 0x0f) << 4),
+        disabled_link: bytes[2] & 0x20 != 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // One 6 GHz neighbor on channel 37 with a 13 octet TBTT Information field
+    const RNR: &[u8] = &[
+        0x00, 0x0d, 131, 37, 0xff, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x78, 0x56, 0x34, 0x12,
+        0x42, 0x10,
+    ];
+
+    fn names(fields: &[Field]) -> Vec<&str> {
+        fields.iter().map(|field| field.name.as_str()).collect()
+    }
+
+    #[test]
+    fn parses_neighbor() {
+        let neighbors = parse(RNR);
+        assert_eq!(neighbors.len(), 1);
+        assert_eq!(neighbors[0].operating_class, 131);
+        assert_eq!(neighbors[0].channel, 37);
+        assert_eq!(neighbors[0].bssid.as_deref(), Some("00:11:22:33:44:55"));
+        assert_eq!(neighbors[0].short_ssid, Some(0x1234_5678));
+        assert!(neighbors[0].is_same_ssid());
+        assert!(neighbors[0].is_colocated());
+
+        let fields = fields(RNR);
+        assert_eq!(fields.len(), 1);
+        let tbtt = &fields[0].children[4];
+        assert_eq!(tbtt.range, Some(4..17));
+        assert_eq!(
+            names(&tbtt.children),
+            vec![
+                "Neighbor AP TBTT Offset",
+                "BSSID",
+                "Short SSID",
+                "BSS Parameters",
+                "20 MHz PSD"
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_zero_length_tbtt_information() {
+        // Three TBTT Information fields of length 0, followed by the neighbor above
+        let mut bytes = vec![0x20, 0x00, 131, 33];
+        bytes.extend_from_slice(RNR);
+
+        let neighbors = parse(&bytes);
+        assert_eq!(neighbors.len(), 1);
+        assert_eq!(neighbors[0].channel, 37);
+
+        let fields = fields(&bytes);
+        assert_eq!(fields.len(), 2);
+        assert_eq!(
+            names(&fields[0].children),
+            vec![
+                "TBTT Information Count",
+                "TBTT Information Length",
+                "Operating Class",
+                "Channel"
+            ]
+        );
+        assert_eq!(fields[1].children.len(), 5);
+    }
+
+    #[test]
+    fn handles_truncated_elements() {
+        for end in 0..RNR.len() {
+            let bytes = &RNR[..end];
+            assert!(parse(bytes).is_empty());
+            let fields = fields(bytes);
+            assert!(fields.iter().all(|field| field.children.len() == 4));
+            assert_eq!(fields.len(), if end >= 4 { 1 } else { 0 });
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use crate::decode::{
+    self,
+    field::{hex, mac},
+    read_u16,
+    vendor::{MICROSOFT_OUI, WPA_OUI_TYPE},
+    Field,
+};
+use kawaiifi::Ie;
+
+pub const RSN_ID: u8 = 48;
+pub const IEEE_OUI: [u8; 3] = [0x00, 0x0f, 0xac];
+
+pub const CIPHER_WEP_40: u8 = 1;
+pub const CIPHER_TKIP: u8 = 2;
+pub const CIPHER_CCMP_128: u8 = 4;
+pub const CIPHER_WEP_104: u8 = 5;
+
+pub const AKM_8021X: u8 = 1;
+pub const AKM_PSK: u8 = 2;
+pub const AKM_FT_8021X: u8 = 3;
+pub const AKM_FT_PSK: u8 = 4;
+pub const AKM_8021X_SHA256: u8 = 5;
+pub const AKM_SAE: u8 = 8;
+pub const AKM_FT_SAE: u8 = 9;
+pub const AKM_8021X_SUITE_B: u8 = 11;
+pub const AKM_8021X_SUITE_B_192: u8 = 12;
+pub const AKM_FT_8021X_SHA384: u8 = 13;
+pub const AKM_OWE: u8 = 18;
+pub const AKM_SAE_EXT_KEY: u8 = 24;
+pub const AKM_FT_SAE_EXT_KEY: u8 = 25;
+
+const CAPABILITY_MFPR: u16 = 0x0040;
+const CAPABILITY_MFPC: u16 = 0x0080;
+
+const CAPABILITIES: &[(u32, &str)] = &[
+    (0x0001, "Preauthentication"),
+    (0x0002, "No Pairwise"),
+    (0x0040, "MFP Required"),
+    (0x0080, "MFP Capable"),
+    (0x0100, "Joint Multi-band RSNA"),
+    (0x0200, "PeerKey Enabled"),
+    (0x0400, "SPP A-MSDU Capable"),
+    (0x0800, "SPP A-MSDU Required"),
+    (0x1000, "PBAC"),
+    (0x2000, "Extended Key ID"),
+    (0x4000, "OCVC"),
+];
+
+// The RSN element, or the pre-standard WPA vendor element which shares its layout up to the
+// AKM suites. The PMKID list and group management cipher that may follow the RSN capabilities
+// aren't needed.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Rsn {
+    pub group_cipher: Option<[u8; 4]>,
+    pub pairwise_ciphers: Vec<[u8; 4]>,
+    pub akm_suites: Vec<[u8; 4]>,
+    pub capabilities: Option<u16>,
+}
+
+impl Rsn {
+    pub fn from_ies(ies: &[Ie]) -> Option<Rsn> {
+        decode::find_ie(ies, RSN_ID).and_then(Rsn::parse)
+    }
+
+    pub fn wpa_from_ies(ies: &[Ie]) -> Option<Rsn> {
+        decode::find_vendor_ie(ies, MICROSOFT_OUI, WPA_OUI_TYPE).and_then(Rsn::parse)
+    }
+
+    // Every field after the version is optional, truncated elements use the defaults
+    pub fn parse(bytes: &[u8]) -> Option<Rsn> {
+        // Version
+        read_u16(bytes, 0)?;
+
+        let mut rsn = Rsn {
+            group_cipher: suite(bytes, 2),
+            ..Rsn::default()
+        };
+
+        let mut offset = 6;
+        rsn.pairwise_ciphers = suite_list(bytes, &mut offset);
+        rsn.akm_suites = suite_list(bytes, &mut offset);
+        rsn.capabilities = read_u16(bytes, offset);
+
+        Some(rsn)
+    }
+
+    pub fn mfp_capable(&self) -> bool {
+        self.capabilities.unwrap_or_default() & CAPABILITY_MFPC != 0
+    }
+
+    pub fn mfp_required(&self) -> bool {
+        self.capabilities.unwrap_or_default() & CAPABILITY_MFPR != 0
+    }
+
+    pub fn has_akm(&self, akm: u8) -> bool {
+        self.akm_suites
+            .iter()
+            .any(|suite| suite_type(suite) == Some(akm))
+    }
+
+    pub fn has_pairwise_cipher(&self, cipher: u8) -> bool {
+        self.pairwise_ciphers
+            .iter()
+            .any(|suite| suite_type(suite) == Some(cipher))
+    }
+
+    pub fn group_cipher_type(&self) -> Option<u8> {
+        self.group_cipher
+            .as_ref()
+            .and_then(|suite| suite_type(suite))
+    }
+
+    pub fn is_sae(&self) -> bool {
+        [AKM_SAE, AKM_FT_SAE, AKM_SAE_EXT_KEY, AKM_FT_SAE_EXT_KEY]
+            .iter()
+            .any(|akm| self.has_akm(*akm))
+    }
+
+    pub fn is_psk(&self) -> bool {
+        self.has_akm(AKM_PSK) || self.has_akm(AKM_FT_PSK)
+    }
+
+    pub fn is_enterprise(&self) -> bool {
+        [
+            AKM_8021X,
+            AKM_FT_8021X,
+            AKM_8021X_SHA256,
+            AKM_8021X_SUITE_B,
+            AKM_8021X_SUITE_B_192,
+            AKM_FT_8021X_SHA384,
+        ]
+        .iter()
+        .any(|akm| self.has_akm(*akm))
+    }
+
+    pub fn is_ft(&self) -> bool {
+        [AKM_FT_8021X, AKM_FT_PSK, AKM_FT_SAE, AKM_FT_SAE_EXT_KEY]
+            .iter()
+            .any(|akm| self.has_akm(*akm))
+    }
+}
+
+// The suite type of a suite from the IEEE or (for WPA) Microsoft OUI
+pub fn suite_type(suite: &[u8]) -> Option<u8> {
+    if suite[0..3] == IEEE_OUI || suite[0..3] == MICROSOFT_OUI {
+        Some(suite[3])
+    } else {
+        None
+    }
+}
+
+pub fn cipher_suite_name(suite: &[u8]) -> String {
+    let name = match suite_type(suite) {
+        Some(0) => Some("Use group cipher"),
+        Some(CIPHER_WEP_40) => Some("WEP-40"),
+        Some(CIPHER_TKIP) => Some("TKIP"),
+        Some(CIPHER_CCMP_128) => Some("CCMP-128"),
+        Some(CIPHER_WEP_104) => Some("WEP-104"),
+        Some(6) => Some("BIP-CMAC-128"),
+        Some(8) => Some("GCMP-128"),
+        Some(9) => Some("GCMP-256"),
+        Some(10) => Some("CCMP-256"),
+        Some(11) => Some("BIP-GMAC-128"),
+        Some(12) => Some("BIP-GMAC-256"),
+        Some(13) => Some("BIP-CMAC-256"),
+        _ => None,
+    };
+
+    name.map(|name| name.to_string())
+        .unwrap_or_else(|| format!("{}-{}", mac(&suite[0..3]), suite[3]))
+}
+
+pub fn akm_suite_name(suite: &[u8]) -> String {
+    let name = match suite_type(suite) {
+        Some(AKM_8021X) => Some("802.1X"),
+        Some(AKM_PSK) => Some("PSK"),
+        Some(AKM_FT_8021X) => Some("FT-802.1X"),
+        Some(AKM_FT_PSK) => Some("FT-PSK"),
+        Some(AKM_8021X_SHA256) => Some("802.1X-SHA256"),
+        Some(6) => Some("PSK-SHA256"),
+        Some(AKM_SAE) => Some("SAE"),
+        Some(AKM_FT_SAE) => Some("FT-SAE"),
+        Some(AKM_8021X_SUITE_B) => Some("802.1X-Suite-B"),
+        Some(AKM_8021X_SUITE_B_192) => Some("802.1X-Suite-B-192"),
+        Some(AKM_FT_8021X_SHA384) => Some("FT-802.1X-SHA384"),
+        Some(AKM_OWE) => Some("OWE"),
+        Some(AKM_SAE_EXT_KEY) => Some("SAE-EXT-KEY"),
+        Some(AKM_FT_SAE_EXT_KEY) => Some("FT-SAE-EXT-KEY"),
+        _ => None,
+    };
+
+    name.map(|name| name.to_string())
+        .unwrap_or_else(|| format!("{}-{}", mac(&suite[0..3]), suite[3]))
+}
+
+// The fields of an RSN element, or of a WPA element after its OUI and OUI type
+pub fn fields(bytes: &[u8]) -> Vec<Field> {
+    let mut fields = Vec::new();
+    match read_u16(bytes, 0) {
+        Some(version) => fields.push(Field::new("Version", version).at(0..2)),
+        None => return fields,
+    }
+    match bytes.get(2..6) {
+        Some(group) => fields.push(Field::new("Group Cipher", cipher_suite_name(group)).at(2..6)),
+        None => return fields,
+    }
+
+    let mut offset = 6;
+    for (name, suite_name) in &[
+        ("Pairwise Ciphers", cipher_suite_name as fn(&[u8]) -> String),
+        ("AKM Suites", akm_suite_name),
+    ] {
+        match suite_list_field(bytes, &mut offset, name, *suite_name) {
+            Some(field) => fields.push(field),
+            None => return fields,
+        }
+    }
+
+    let capabilities = match read_u16(bytes, offset) {
+        Some(capabilities) => capabilities,
+        None => return fields,
+    };
+    let mut capability_fields = Field::flags(capabilities as u32, CAPABILITIES, offset..offset + 2);
+    capability_fields.push(
+        Field::new("PTKSA Replay Counters", replay_counters(capabilities >> 2))
+            .at(offset..offset + 1),
+    );
+    capability_fields.push(
+        Field::new("GTKSA Replay Counters", replay_counters(capabilities >> 4))
+            .at(offset..offset + 1),
+    );
+    fields.push(
+        Field::new("RSN Capabilities", format!("{:#06x}", capabilities))
+            .at(offset..offset + 2)
+            .with_children(capability_fields),
+    );
+    offset += 2;
+
+    if let Some(count) = read_u16(bytes, offset) {
+        let end = (offset + 2 + count as usize * 16).min(bytes.len());
+        fields.push(
+            Field::new("PMKID Count", count)
+                .at(offset..end)
+                .with_children(
+                    bytes[offset + 2..end]
+                        .chunks_exact(16)
+                        .enumerate()
+                        .map(|(i, pmkid)| {
+                            let at = offset + 2 + i * 16;
+                            Field::new("PMKID", hex(pmkid)).at(at..at + 16)
+                        })
+                        .collect(),
+                ),
+        );
+        offset = end;
+    }
+
+    if let Some(suite) = bytes.get(offset..offset + 4) {
+        fields.push(
+            Field::new("Group Management Cipher", cipher_suite_name(suite)).at(offset..offset + 4),
+        );
+    }
+
+    fields
+}
+
+// A suite count followed by that many suites, with one child per suite
+fn suite_list_field(
+    bytes: &[u8],
+    offset: &mut usize,
+    name: &str,
+    suite_name: fn(&[u8]) -> String,
+) -> Option<Field> {
+    let start = *offset;
+    let count = read_u16(bytes, start)? as usize;
+    let end = (start + 2 + count * 4).min(bytes.len());
+    *offset = end;
+
+    let suites = bytes[start + 2..end]
+        .chunks_exact(4)
+        .enumerate()
+        .map(|(i, suite)| {
+            let at = start + 2 + i * 4;
+            Field::new(&format!("Suite {}", i + 1), suite_name(suite)).at(at..at + 4)
+        })
+        .collect::<Vec<Field>>();
+    Some(
+        Field::new(
+            name,
+            suites
+                .iter()
+                .map(|suite| suite.value.as_str())
+                .collect::<Vec<&str>>()
+                .join(", "),
+        )
+        .at(start..end)
+        .with_children(suites),
+    )
+}
+
+fn suite(bytes: &[u8], offset: usize) -> Option<[u8; 4]> {
+    let suite = bytes.get(offset..offset + 4)?;
+    Some([suite[0], suite[1], suite[2], suite[3]])
+}
+
+fn suite_list(bytes: &[u8], offset: &mut usize) -> Vec<[u8; 4]> {
+    let count = match read_u16(bytes, *offset) {
+        Some(count) => count as usize,
+        None => return Vec::new(),
+    };
+    *offset += 2;
+
+    let suites = (0..count)
+        .filter_map(|i| suite(bytes, *offset + i * 4))
+        .collect();
+    *offset += count * 4;
+    suites
+}
+
+// The 2-bit replay counter fields encode 1, 2, 4 or 16 counters
+fn replay_counters(bits: u16) -> u8 {
+    match bits & 0x03 {
+        0 => 1,
+        1 => 2,
+        2 => 4,
+        _ => 16,
+    }
+}
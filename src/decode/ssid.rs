@@ -0,0 +1,90 @@
+use crate::decode::{self, capabilities, field::hex, Field};
+use kawaiifi::Ie;
+use unicode_width::UnicodeWidthChar;
+
+pub const SSID_ID: u8 = 0;
+
+const EXTENDED_CAPABILITY_UTF8_SSID: usize = 48;
+
+// The SSID of a BSS made safe to print to a terminal
+pub fn display_ssid(ies: &[Ie]) -> Option<String> {
+    let bytes = decode::find_ie(ies, SSID_ID)?;
+    Some(escape(bytes, is_utf8(ies)))
+}
+
+fn is_utf8(ies: &[Ie]) -> bool {
+    capabilities::extended_capability(ies, EXTENDED_CAPABILITY_UTF8_SSID)
+}
+
+// SSIDs are arbitrary octets, so escape anything that could control the terminal. An SSID that
+// isn't UTF-8 is shown as hex, unless the AP declares its SSIDs UTF-8 and only the invalid
+// octets are escaped.
+pub fn escape(bytes: &[u8], utf8: bool) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => escape_text(text),
+        Err(_) if utf8 => {
+            let mut escaped = String::new();
+            let mut rest = bytes;
+            while let Err(error) = std::str::from_utf8(rest) {
+                let (valid, invalid) = rest.split_at(error.valid_up_to());
+                escaped.push_str(&escape_text(std::str::from_utf8(valid).unwrap_or_default()));
+                let invalid_len = error.error_len().unwrap_or(invalid.len());
+                for byte in &invalid[..invalid_len] {
+                    escaped.push_str(&format!("\\x{:02x}", byte));
+                }
+                rest = &invalid[invalid_len..];
+            }
+            escaped.push_str(&escape_text(std::str::from_utf8(rest).unwrap_or_default()));
+            escaped
+        }
+        Err(_) => format!("<hex {}>", hex(bytes)),
+    }
+}
+
+pub fn escape_text(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\\' => "\\\\".to_string(),
+            c if c.is_control() && (c as u32) < 0x100 => format!("\\x{:02x}", c as u32),
+            // Other control characters, zero width characters and bidirectional overrides,
+            // which could disguise the SSID
+            c if c.is_control()
+                || matches!(
+                    c,
+                    '\u{200b}'..='\u{200f}'
+                        | '\u{202a}'..='\u{202e}'
+                        | '\u{2066}'..='\u{2069}'
+                        | '\u{feff}'
+                ) =>
+            {
+                format!("\\u{{{:x}}}", c as u32)
+            }
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+// Cuts the text down to the given number of terminal columns, counting wide characters twice
+pub fn truncate_to_width(text: &str, width: usize) -> String {
+    let char_width = |c: char| c.width().unwrap_or_default();
+    if text.chars().map(char_width).sum::<usize>() <= width {
+        return text.to_string();
+    }
+
+    // Leave a column for the ellipsis
+    let mut truncated = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        if used + char_width(c) + 1 > width {
+            break;
+        }
+        used += char_width(c);
+        truncated.push(c);
+    }
+    truncated.push('…');
+    truncated
+}
+
+pub fn fields(bytes: &[u8], ies: &[Ie]) -> Vec<Field> {
+    vec![Field::new("SSID", escape(bytes, is_utf8(ies))).at(0..bytes.len())]
+}
@@ -0,0 +1,486 @@
+use crate::decode::{
+    field::{hex, mac},
+    read_u16, rsn,
+    ssid::escape,
+    Field, WmmParameters, VENDOR_SPECIFIC_ID,
+};
+use kawaiifi::{Ie, InformationElement};
+
+pub const MICROSOFT_OUI: [u8; 3] = [0x00, 0x50, 0xf2];
+pub const WIFI_ALLIANCE_OUI: [u8; 3] = [0x50, 0x6f, 0x9a];
+
+pub const WPA_OUI_TYPE: u8 = 1;
+pub const WMM_OUI_TYPE: u8 = 2;
+pub const WPS_OUI_TYPE: u8 = 4;
+pub const P2P_OUI_TYPE: u8 = 9;
+pub const HOTSPOT_20_OUI_TYPE: u8 = 0x10;
+pub const OSEN_OUI_TYPE: u8 = 0x12;
+pub const MBO_OCE_OUI_TYPE: u8 = 0x16;
+pub const OWE_TRANSITION_OUI_TYPE: u8 = 0x1c;
+
+pub const WMM_INFORMATION_SUBTYPE: u8 = 0;
+pub const WMM_PARAMETER_SUBTYPE: u8 = 1;
+
+const VENDORS: &[([u8; 3], &str)] = &[
+    ([0x00, 0x50, 0xf2], "Microsoft"),
+    ([0x50, 0x6f, 0x9a], "Wi-Fi Alliance"),
+    ([0x00, 0x03, 0x7f], "Atheros"),
+    ([0x00, 0x0b, 0x86], "Aruba"),
+    ([0x00, 0x0c, 0x43], "MediaTek"),
+    ([0x00, 0x0c, 0xe7], "MediaTek"),
+    ([0x00, 0x10, 0x18], "Broadcom"),
+    ([0x00, 0x13, 0x92], "Ruckus"),
+    ([0x00, 0x15, 0x6d], "Ubiquiti"),
+    ([0x00, 0x17, 0xf2], "Apple"),
+    ([0x00, 0x18, 0x0a], "Meraki"),
+    ([0x00, 0x19, 0x77], "Aerohive"),
+    ([0x00, 0x1a, 0x1e], "Aruba"),
+    ([0x00, 0x26, 0x86], "Quantenna"),
+    ([0x00, 0x40, 0x96], "Cisco"),
+    ([0x00, 0x90, 0x4c], "Epigram"),
+    ([0x00, 0x09, 0x0f], "Fortinet"),
+    ([0x00, 0xe0, 0x4c], "Realtek"),
+    ([0x5c, 0x5b, 0x35], "Mist"),
+    ([0x8c, 0xfd, 0xf0], "Qualcomm"),
+];
+
+pub fn vendor_name(oui: &[u8]) -> Option<&'static str> {
+    VENDORS
+        .iter()
+        .find(|(vendor_oui, _)| vendor_oui == oui)
+        .map(|(_, name)| *name)
+}
+
+// The vendors of the vendor specific IEs, leaving out the Microsoft and Wi-Fi Alliance elements
+// that every vendor uses
+pub fn vendors(ies: &[Ie]) -> Vec<&'static str> {
+    let mut vendors = ies
+        .iter()
+        .filter(|ie| ie.id() == VENDOR_SPECIFIC_ID)
+        .filter_map(|ie| ie.bytes().get(0..3).and_then(vendor_name))
+        .filter(|vendor| *vendor != "Microsoft" && *vendor != "Wi-Fi Alliance")
+        .collect::<Vec<&'static str>>();
+    vendors.sort_unstable();
+    vendors.dedup();
+    vendors
+}
+
+// The name shown in the IE table, e.g. "Vendor: WPS (Microsoft)"
+pub fn name(bytes: &[u8]) -> String {
+    let oui = match bytes.get(0..3) {
+        Some(oui) => oui,
+        None => return "Vendor Specific".to_string(),
+    };
+
+    match (vendor_name(oui), element_name(bytes)) {
+        (Some(vendor), Some(element)) => format!("Vendor: {} ({})", element, vendor),
+        (Some(vendor), None) => format!("Vendor: {}", vendor),
+        (None, _) => format!("Vendor: {}", mac(oui)),
+    }
+}
+
+fn element_name(bytes: &[u8]) -> Option<&'static str> {
+    let oui_type = *bytes.get(3)?;
+    match &bytes[0..3] {
+        oui if oui == MICROSOFT_OUI => match oui_type {
+            WPA_OUI_TYPE => Some("WPA"),
+            WMM_OUI_TYPE => match bytes.get(4) {
+                Some(&WMM_INFORMATION_SUBTYPE) => Some("WMM Information"),
+                Some(&WMM_PARAMETER_SUBTYPE) => Some("WMM Parameter"),
+                _ => Some("WMM"),
+            },
+            WPS_OUI_TYPE => Some("WPS"),
+            _ => None,
+        },
+        oui if oui == WIFI_ALLIANCE_OUI => match oui_type {
+            P2P_OUI_TYPE => Some("P2P"),
+            HOTSPOT_20_OUI_TYPE => Some("Hotspot 2.0 Indication"),
+            OSEN_OUI_TYPE => Some("OSEN"),
+            MBO_OCE_OUI_TYPE => Some("MBO-OCE"),
+            OWE_TRANSITION_OUI_TYPE => Some("OWE Transition Mode"),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+pub fn fields(bytes: &[u8]) -> Vec<Field> {
+    let oui = match bytes.get(0..3) {
+        Some(oui) => oui,
+        None => return vec![Field::new("Data", hex(bytes)).at(0..bytes.len())],
+    };
+    let oui_type = bytes.get(3).copied();
+    let body = bytes.get(4..).unwrap_or_default();
+
+    let mut fields = vec![Field::new(
+        "OUI",
+        match vendor_name(oui) {
+            Some(vendor) => format!("{} ({})", mac(oui), vendor),
+            None => mac(oui),
+        },
+    )
+    .at(0..3)];
+
+    let decoded = match (oui, oui_type) {
+        (oui, Some(WPA_OUI_TYPE)) if oui == MICROSOFT_OUI => Some(rsn::fields(body)),
+        (oui, Some(WMM_OUI_TYPE)) if oui == MICROSOFT_OUI => wmm_fields(body),
+        (oui, Some(WPS_OUI_TYPE)) if oui == MICROSOFT_OUI => wps_fields(body),
+        (oui, Some(P2P_OUI_TYPE)) if oui == WIFI_ALLIANCE_OUI => p2p_fields(body),
+        (oui, Some(MBO_OCE_OUI_TYPE)) if oui == WIFI_ALLIANCE_OUI => mbo_oce_fields(body),
+        (oui, Some(OWE_TRANSITION_OUI_TYPE)) if oui == WIFI_ALLIANCE_OUI => {
+            owe_transition_fields(body)
+        }
+        _ => None,
+    };
+
+    match decoded {
+        Some(decoded) => fields.extend(decoded.into_iter().map(|field| field.offset(4))),
+        None => {
+            if let Some(oui_type) = oui_type {
+                fields.push(Field::new("OUI Type", oui_type).at(3..4));
+            }
+            fields.push(Field::new("Data", hex(body)).at(4..bytes.len()));
+        }
+    }
+
+    fields
+}
+
+fn wmm_fields(bytes: &[u8]) -> Option<Vec<Field>> {
+    let subtype = *bytes.first()?;
+    let mut fields = vec![Field::new("Version", *bytes.get(1)?).at(1..2)];
+
+    match subtype {
+        WMM_INFORMATION_SUBTYPE => {
+            let qos_info = *bytes.get(2)?;
+            fields.push(Field::new("Parameter Set Count", qos_info & 0x0f).at(2..3));
+            fields.push(Field::new("U-APSD", qos_info & 0x80 != 0).at(2..3));
+        }
+        WMM_PARAMETER_SUBTYPE => fields.extend(
+            WmmParameters::parse(&bytes[2..])?
+                .fields()
+                .into_iter()
+                .map(|field| field.offset(2)),
+        ),
+        _ => fields.push(Field::new("Data", hex(&bytes[2..])).at(2..bytes.len())),
+    }
+
+    Some(fields)
+}
+
+fn wps_fields(bytes: &[u8]) -> Option<Vec<Field>> {
+    let mut fields = Vec::new();
+    let mut offset = 0;
+
+    // WPS attributes are big endian type-length-value triplets
+    while offset + 4 <= bytes.len() {
+        let attribute_type = u16::from_be_bytes([bytes[offset], bytes[offset + 1]]);
+        let length = u16::from_be_bytes([bytes[offset + 2], bytes[offset + 3]]) as usize;
+        let start = offset;
+        let value = bytes.get(offset + 4..offset + 4 + length)?;
+        offset += 4 + length;
+
+        let be_u16 = || {
+            value
+                .get(0..2)
+                .map(|v| u16::from_be_bytes([v[0], v[1]]))
+                .unwrap_or_default()
+        };
+        let field = match attribute_type {
+            0x1008 => Field::new("Config Methods", format!("{:#06x}", be_u16())).with_children(
+                Field::flags(be_u16() as u32, WPS_CONFIG_METHODS, start + 4..offset),
+            ),
+            0x1011 => Field::new("Device Name", escape(value, true)),
+            0x1012 => Field::new("Device Password ID", be_u16()),
+            0x1021 => Field::new("Manufacturer", escape(value, true)),
+            0x1023 => Field::new("Model Name", escape(value, true)),
+            0x1024 => Field::new("Model Number", escape(value, true)),
+            0x103b => Field::new("Response Type", value.first().copied().unwrap_or_default()),
+            0x1041 => Field::new("Selected Registrar", value.first() == Some(&1)),
+            0x1042 => Field::new("Serial Number", escape(value, true)),
+            0x1044 => Field::new(
+                "Wi-Fi Protected Setup State",
+                match value.first() {
+                    Some(1) => "Not configured",
+                    Some(2) => "Configured",
+                    _ => "Unknown",
+                },
+            ),
+            0x1047 => Field::new("UUID-E", hex(value)),
+            0x104a => Field::new(
+                "Version",
+                value
+                    .first()
+                    .map(|v| format!("{}.{}", v >> 4, v & 0x0f))
+                    .unwrap_or_default(),
+            ),
+            0x1053 => Field::new(
+                "Selected Registrar Config Methods",
+                format!("{:#06x}", be_u16()),
+            )
+            .with_children(Field::flags(
+                be_u16() as u32,
+                WPS_CONFIG_METHODS,
+                start + 4..offset,
+            )),
+            0x1054 => Field::new("Primary Device Type", hex(value)),
+            0x1057 => Field::new("AP Setup Locked", value.first() == Some(&1)),
+            0x1049 => Field::new("Vendor Extension", mac(value.get(0..3).unwrap_or_default()))
+                .with_children(wps_vendor_extension_fields(value, start + 4)),
+            _ => Field::new(&format!("Attribute {:#06x}", attribute_type), hex(value)),
+        };
+        fields.push(field.at(start..offset));
+    }
+
+    Some(fields)
+}
+
+// Returns the value of the first WPS attribute of the given type in a WPS IE body
+pub fn wps_attribute(bytes: &[u8], attribute_type: u16) -> Option<&[u8]> {
+    let mut offset = 0;
+    while offset + 4 <= bytes.len() {
+        let length = u16::from_be_bytes([bytes[offset + 2], bytes[offset + 3]]) as usize;
+        let value = bytes.get(offset + 4..offset + 4 + length)?;
+        if u16::from_be_bytes([bytes[offset], bytes[offset + 1]]) == attribute_type {
+            return Some(value);
+        }
+        offset += 4 + length;
+    }
+    None
+}
+
+const WPS_CONFIG_METHODS: &[(u32, &str)] = &[
+    (0x0001, "USBA"),
+    (0x0002, "Ethernet"),
+    (0x0004, "Label"),
+    (0x0008, "Display"),
+    (0x0010, "External NFC Token"),
+    (0x0020, "Integrated NFC Token"),
+    (0x0040, "NFC Interface"),
+    (0x0080, "Push Button"),
+    (0x0100, "Keypad"),
+    (0x0200, "Virtual Push Button"),
+    (0x0400, "Physical Push Button"),
+    (0x2000, "Virtual Display PIN"),
+    (0x4000, "Physical Display PIN"),
+];
+
+const P2P_DEVICE_CAPABILITIES: &[(u32, &str)] = &[
+    (0x01, "Service Discovery"),
+    (0x02, "P2P Client Discoverability"),
+    (0x04, "Concurrent Operation"),
+    (0x08, "P2P Infrastructure Managed"),
+    (0x10, "P2P Device Limit"),
+    (0x20, "P2P Invitation Procedure"),
+];
+
+const P2P_GROUP_CAPABILITIES: &[(u32, &str)] = &[
+    (0x01, "P2P Group Owner"),
+    (0x02, "Persistent P2P Group"),
+    (0x04, "P2P Group Limit"),
+    (0x08, "Intra-BSS Distribution"),
+    (0x10, "Cross Connection"),
+    (0x20, "Persistent Reconnect"),
+    (0x40, "Group Formation"),
+    (0x80, "IP Address Allocation"),
+];
+
+// The Wi-Fi Alliance vendor extension carries WPS 2.0 subelements after its vendor ID
+fn wps_vendor_extension_fields(bytes: &[u8], base: usize) -> Vec<Field> {
+    let mut fields = Vec::new();
+    if bytes.get(0..3) != Some(&[0x00, 0x37, 0x2a][..]) {
+        return fields;
+    }
+
+    let mut offset = 3;
+    while offset + 2 <= bytes.len() {
+        let start = offset;
+        let id = bytes[offset];
+        let length = bytes[offset + 1] as usize;
+        let value = match bytes.get(offset + 2..offset + 2 + length) {
+            Some(value) => value,
+            None => break,
+        };
+        offset += 2 + length;
+
+        let first = value.first().copied().unwrap_or_default();
+        let field = match id {
+            0 => Field::new("Version2", format!("{}.{}", first >> 4, first & 0x0f)),
+            1 => Field::new(
+                "Authorized MACs",
+                value
+                    .chunks_exact(6)
+                    .map(mac)
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            ),
+            2 => Field::new("Network Key Shareable", first == 1),
+            3 => Field::new("Request to Enroll", first == 1),
+            4 => Field::new("Settings Delay Time", format!("{} s", first)),
+            _ => Field::new(&format!("Subelement {}", id), hex(value)),
+        };
+        fields.push(field.at(base + start..base + offset));
+    }
+
+    fields
+}
+
+fn p2p_fields(bytes: &[u8]) -> Option<Vec<Field>> {
+    let mut fields = Vec::new();
+    let mut offset = 0;
+
+    // P2P attributes are an ID octet followed by a little endian length
+    while offset + 3 <= bytes.len() {
+        let attribute_id = bytes[offset];
+        let length = read_u16(bytes, offset + 1)? as usize;
+        let start = offset;
+        let value = bytes.get(offset + 3..offset + 3 + length)?;
+        offset += 3 + length;
+
+        let field = match attribute_id {
+            0 => Field::new("Status", value.first().copied().unwrap_or_default()),
+            2 => {
+                let device_capability = value.first().copied().unwrap_or_default();
+                let group_capability = value.get(1).copied().unwrap_or_default();
+                Field::new(
+                    "P2P Capability",
+                    format!(
+                        "Device {:#04x}, Group {:#04x}",
+                        device_capability, group_capability
+                    ),
+                )
+                .with_children(vec![
+                    Field::new("Device Capability", format!("{:#04x}", device_capability))
+                        .at(start + 3..start + 4)
+                        .with_children(Field::flags(
+                            device_capability as u32,
+                            P2P_DEVICE_CAPABILITIES,
+                            start + 3..start + 4,
+                        )),
+                    Field::new("Group Capability", format!("{:#04x}", group_capability))
+                        .at(start + 4..start + 5)
+                        .with_children(Field::flags(
+                            group_capability as u32,
+                            P2P_GROUP_CAPABILITIES,
+                            start + 4..start + 5,
+                        )),
+                ])
+            }
+            3 => Field::new("P2P Device ID", mac(value)),
+            6 => Field::new("Listen Channel", hex(value)),
+            10 => Field::new("Extended Listen Timing", hex(value)),
+            11 => Field::new("Intended P2P Interface Address", mac(value)),
+            12 => Field::new("Notice of Absence", hex(value)),
+            13 => Field::new("P2P Device Info", hex(value)),
+            14 => Field::new("P2P Group Info", hex(value)),
+            17 => Field::new("Operating Channel", hex(value)),
+            _ => Field::new(&format!("Attribute {}", attribute_id), hex(value)),
+        };
+        fields.push(field.at(start..offset));
+    }
+
+    Some(fields)
+}
+
+fn mbo_oce_fields(bytes: &[u8]) -> Option<Vec<Field>> {
+    let mut fields = Vec::new();
+    let mut offset = 0;
+
+    while offset + 2 <= bytes.len() {
+        let attribute_id = bytes[offset];
+        let length = bytes[offset + 1] as usize;
+        let start = offset;
+        let value = bytes.get(offset + 2..offset + 2 + length)?;
+        offset += 2 + length;
+
+        let first = value.first().copied().unwrap_or_default();
+        let field = match attribute_id {
+            1 => Field::new(
+                "AP Capability Indication",
+                format!("Cellular Data Aware: {}", first & 0x40 != 0),
+            ),
+            2 => Field::new("Non-preferred Channel Report", hex(value)),
+            3 => Field::new("Cellular Data Capabilities", first),
+            4 => Field::new(
+                "Association Disallowed",
+                match first {
+                    1 => "Unspecified",
+                    2 => "Maximum number of STAs reached",
+                    3 => "Air interface overloaded",
+                    4 => "Authentication server overloaded",
+                    5 => "Insufficient RSSI",
+                    _ => "Reserved",
+                },
+            ),
+            5 => Field::new("Cellular Data Connection Preference", first),
+            6 => Field::new("Transition Reason Code", first),
+            7 => Field::new("Transition Rejection Reason Code", first),
+            8 => Field::new(
+                "Association Retry Delay",
+                format!("{} s", read_u16(value, 0)?),
+            ),
+            101 => Field::new(
+                "OCE Capability Indication",
+                format!("Version {}, {:#04x}", first & 0x07, first),
+            ),
+            102 => Field::new("RSSI-based Association Rejection", hex(value)),
+            103 => Field::new("Reduced WAN Metrics", hex(value)),
+            104 => Field::new("RNR Completeness", hex(value)),
+            _ => Field::new(&format!("Attribute {}", attribute_id), hex(value)),
+        };
+        fields.push(field.at(start..offset));
+    }
+
+    Some(fields)
+}
+
+fn owe_transition_fields(bytes: &[u8]) -> Option<Vec<Field>> {
+    let ssid_length = *bytes.get(6)? as usize;
+    let ssid = bytes.get(7..7 + ssid_length)?;
+    let mut fields = vec![
+        Field::new("BSSID", mac(bytes.get(0..6)?)).at(0..6),
+        Field::new("SSID", escape(ssid, false)).at(6..7 + ssid_length),
+    ];
+
+    if let Some(band_channel) = bytes.get(7 + ssid_length..9 + ssid_length) {
+        fields.push(
+            Field::new("Operating Class", band_channel[0]).at(7 + ssid_length..8 + ssid_length),
+        );
+        fields.push(Field::new("Channel", band_channel[1]).at(8 + ssid_length..9 + ssid_length));
+    }
+
+    Some(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_wps_strings() {
+        // A WPS element whose Device Name tries to clear the screen
+        let mut bytes = vec![0x00, 0x50, 0xf2, WPS_OUI_TYPE, 0x10, 0x11, 0x00, 0x07];
+        bytes.extend_from_slice(b"\x1b[2JAP\x07");
+
+        let fields = fields(&bytes);
+        let device_name = fields
+            .iter()
+            .find(|field| field.name == "Device Name")
+            .unwrap();
+        assert_eq!(device_name.value, "\\x1b[2JAP\\x07");
+        assert!(fields
+            .iter()
+            .all(|field| !field.value.contains('\u{1b}') && !field.value.contains('\u{7}')));
+    }
+
+    #[test]
+    fn escapes_owe_transition_ssid() {
+        let mut bytes = vec![0x50, 0x6f, 0x9a, OWE_TRANSITION_OUI_TYPE];
+        bytes.extend_from_slice(&[0x02, 0x11, 0x22, 0x33, 0x44, 0x55, 0x03]);
+        bytes.extend_from_slice(b"a\x1bb");
+
+        let fields = fields(&bytes);
+        let ssid = fields.iter().find(|field| field.name == "SSID").unwrap();
+        assert_eq!(ssid.value, "a\\x1bb");
+    }
+}
@@ -0,0 +1,178 @@
+use crate::decode::{
+    self, read_u16,
+    vendor::{MICROSOFT_OUI, WMM_OUI_TYPE, WMM_PARAMETER_SUBTYPE},
+    Field,
+};
+use kawaiifi::Ie;
+use std::fmt::Display;
+
+const MU_EDCA_PARAMETER_SET_EXT_ID: u8 = 38;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum AccessCategory {
+    BestEffort,
+    Background,
+    Video,
+    Voice,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct AcParameters {
+    pub access_category: AccessCategory,
+    pub aifsn: u8,
+    pub acm: bool,
+    pub cw_min: u16,
+    pub cw_max: u16,
+    pub txop_limit_us: u32,
+}
+
+// An MU AC Parameter Record, which replaces the TXOP limit with the MU EDCA timer
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct MuAcParameters {
+    pub access_category: AccessCategory,
+    pub aifsn: u8,
+    pub acm: bool,
+    pub cw_min: u16,
+    pub cw_max: u16,
+    pub timer_us: u32,
+}
+
+// The HE MU EDCA Parameter Set element, used by HE stations after a trigger based transmission
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MuEdcaParameters {
+    pub qos_info: u8,
+    pub ac_parameters: Vec<MuAcParameters>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct WmmParameters {
+    pub qos_info: u8,
+    pub ac_parameters: Vec<AcParameters>,
+}
+
+impl WmmParameters {
+    // Parses the body of a WMM Parameter element following the OUI subtype and version octets
+    pub fn parse(bytes: &[u8]) -> Option<WmmParameters> {
+        let qos_info = *bytes.first()?;
+        // Skip the QoS Info and reserved octets
+        let ac_parameters = bytes
+            .get(2..)?
+            .chunks_exact(4)
+            .filter_map(AcParameters::parse)
+            .collect::<Vec<AcParameters>>();
+
+        Some(WmmParameters {
+            qos_info,
+            ac_parameters,
+        })
+    }
+
+    pub fn from_ies(ies: &[Ie]) -> Option<WmmParameters> {
+        let bytes = decode::find_vendor_ie(ies, MICROSOFT_OUI, WMM_OUI_TYPE)?;
+        if *bytes.first()? == WMM_PARAMETER_SUBTYPE {
+            WmmParameters::parse(bytes.get(2..)?)
+        } else {
+            None
+        }
+    }
+
+    pub fn uapsd(&self) -> bool {
+        self.qos_info & 0x80 != 0
+    }
+
+    pub fn parameter_set_count(&self) -> u8 {
+        self.qos_info & 0x0f
+    }
+
+    pub fn fields(&self) -> Vec<Field> {
+        let mut fields = vec![
+            Field::new("Parameter Set Count", self.parameter_set_count()).at(0..1),
+            Field::new("U-APSD", self.uapsd()).at(0..1),
+        ];
+        fields.extend(self.ac_parameters.iter().enumerate().map(|(i, ac)| {
+            let offset = 2 + i * 4;
+            Field::new(
+                &ac.access_category.to_string(),
+                format!(
+                    "AIFSN {}, CWmin {}, CWmax {}, TXOP {} us, ACM {}",
+                    ac.aifsn, ac.cw_min, ac.cw_max, ac.txop_limit_us, ac.acm
+                ),
+            )
+            .at(offset..offset + 4)
+            .with_children(vec![
+                Field::new("AIFSN", ac.aifsn).at(offset..offset + 1),
+                Field::new("ACM", ac.acm).at(offset..offset + 1),
+                Field::new("CWmin", ac.cw_min).at(offset + 1..offset + 2),
+                Field::new("CWmax", ac.cw_max).at(offset + 1..offset + 2),
+                Field::new("TXOP Limit", format!("{} us", ac.txop_limit_us))
+                    .at(offset + 2..offset + 4),
+            ])
+        }));
+        fields
+    }
+}
+
+impl AcParameters {
+    // Parses an AC Parameter Record of the WMM Parameter or EDCA Parameter Set elements
+    pub fn parse(bytes: &[u8]) -> Option<AcParameters> {
+        let aci_aifsn = *bytes.first()?;
+        let ecw = *bytes.get(1)?;
+        Some(AcParameters {
+            access_category: access_category(aci_aifsn),
+            aifsn: aci_aifsn & 0x0f,
+            acm: aci_aifsn & 0x10 != 0,
+            cw_min: (1 << (ecw & 0x0f)) - 1,
+            cw_max: (1 << (ecw >> 4)) - 1,
+            txop_limit_us: read_u16(bytes, 2)? as u32 * 32,
+        })
+    }
+}
+
+impl MuEdcaParameters {
+    pub fn from_ies(ies: &[Ie]) -> Option<MuEdcaParameters> {
+        MuEdcaParameters::parse(decode::find_ext_ie(ies, MU_EDCA_PARAMETER_SET_EXT_ID)?)
+    }
+
+    // Parses the body of the element following the element ID extension octet
+    pub fn parse(bytes: &[u8]) -> Option<MuEdcaParameters> {
+        let qos_info = *bytes.first()?;
+        let ac_parameters = bytes[1..]
+            .chunks_exact(3)
+            .map(|record| MuAcParameters {
+                access_category: access_category(record[0]),
+                aifsn: record[0] & 0x0f,
+                acm: record[0] & 0x10 != 0,
+                cw_min: (1 << (record[1] & 0x0f)) - 1,
+                cw_max: (1 << (record[1] >> 4)) - 1,
+                // The MU EDCA timer counts in units of 8 TUs
+                timer_us: record[2] as u32 * 8 * 1024,
+            })
+            .collect();
+
+        Some(MuEdcaParameters {
+            qos_info,
+            ac_parameters,
+        })
+    }
+}
+
+// The ACI subfield of an ACI/AIFSN octet
+fn access_category(aci_aifsn: u8) -> AccessCategory {
+    match (aci_aifsn >> 5) & 0x03 {
+        0 => AccessCategory::BestEffort,
+        1 => AccessCategory::Background,
+        2 => AccessCategory::Video,
+        _ => AccessCategory::Voice,
+    }
+}
+
+impl Display for AccessCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccessCategory::BestEffort => write!(f, "AC_BE"),
+            AccessCategory::Background => write!(f, "AC_BK"),
+            AccessCategory::Video => write!(f, "AC_VI"),
+            AccessCategory::Voice => write!(f, "AC_VO"),
+        }
+    }
+}
@@ -0,0 +1,1230 @@
+use kawaiifi::{Bss, Ie};
+
+const SSID_ID: u8 = 0;
+const SUPPORTED_RATES_ID: u8 = 1;
+const DS_PARAMETER_SET_ID: u8 = 3;
+const COUNTRY_ID: u8 = 7;
+const BSS_LOAD_ID: u8 = 11;
+const POWER_CONSTRAINT_ID: u8 = 32;
+const TPC_REPORT_ID: u8 = 35;
+const HT_CAPABILITIES_ID: u8 = 45;
+const RSN_ID: u8 = 48;
+const EXTENDED_SUPPORTED_RATES_ID: u8 = 50;
+const MOBILITY_DOMAIN_ID: u8 = 54;
+const HT_OPERATION_ID: u8 = 61;
+const RM_ENABLED_CAPABILITIES_ID: u8 = 70;
+const EXTENDED_CAPABILITIES_ID: u8 = 127;
+const VHT_CAPABILITIES_ID: u8 = 191;
+const VHT_OPERATION_ID: u8 = 192;
+const VENDOR_SPECIFIC_ID: u8 = 221;
+const ELEMENT_ID_EXTENSION: u8 = 255;
+const HE_CAPABILITIES_EXT_ID: u8 = 35;
+const HE_OPERATION_EXT_ID: u8 = 36;
+
+const IEEE_OUI: [u8; 3] = [0x00, 0x0f, 0xac];
+const MICROSOFT_OUI: [u8; 3] = [0x00, 0x50, 0xf2];
+const WPA_OUI_TYPE: u8 = 1;
+const WMM_OUI_TYPE: u8 = 2;
+
+// iw's names for the cipher and AKM suite types, which it uses for both RSN and WPA
+const CIPHER_SUITES: &[(&str, u8)] = &[
+    ("Use group cipher suite", 0),
+    ("WEP-40", 1),
+    ("TKIP", 2),
+    ("CCMP", 4),
+    ("WEP-104", 5),
+    ("AES-128-CMAC", 6),
+    ("NO-GROUP", 7),
+    ("GCMP-128", 8),
+    ("GCMP-256", 9),
+    ("CCMP-256", 10),
+    ("BIP-GMAC-128", 11),
+    ("BIP-GMAC-256", 12),
+    ("BIP-CMAC-256", 13),
+];
+
+const AKM_SUITES: &[(&str, u8)] = &[
+    ("IEEE 802.1X", 1),
+    ("PSK", 2),
+    ("FT/IEEE 802.1X", 3),
+    ("FT/PSK", 4),
+    ("IEEE 802.1X/SHA-256", 5),
+    ("PSK/SHA-256", 6),
+    ("TDLS/TPK", 7),
+    ("SAE", 8),
+    ("FT/SAE", 9),
+    ("IEEE 802.1X/SUITE-B", 11),
+    ("IEEE 802.1X/SUITE-B-192", 12),
+    ("FT/IEEE 802.1X/SHA-384", 13),
+    ("FILS/SHA-256", 14),
+    ("FILS/SHA-384", 15),
+    ("FT/FILS/SHA-256", 16),
+    ("FT/FILS/SHA-384", 17),
+    ("OWE", 18),
+    ("SAE-EXT-KEY", 24),
+    ("FT/SAE-EXT-KEY", 25),
+];
+
+// iw lists the set bits of the Extended Capabilities element by name
+const EXTENDED_CAPABILITIES: &[(&str, usize)] = &[
+    ("HT Information Exchange Supported", 0),
+    ("Extended Channel Switching", 2),
+    ("PSMP Capability", 4),
+    ("S-PSMP Capability", 6),
+    ("Event", 7),
+    ("Diagnostics", 8),
+    ("Multicast Diagnostics", 9),
+    ("Location Tracking", 10),
+    ("FMS", 11),
+    ("Proxy ARP Service", 12),
+    ("Collocated Interference Reporting", 13),
+    ("Civic Location", 14),
+    ("Geospatial Location", 15),
+    ("TFS", 16),
+    ("WNM-Sleep Mode", 17),
+    ("TIM Broadcast", 18),
+    ("BSS Transition", 19),
+    ("QoS Traffic Capability", 20),
+    ("AC Station Count", 21),
+    ("Multiple BSSID", 22),
+    ("Timing Measurement", 23),
+    ("Channel Usage", 24),
+    ("SSID List", 25),
+    ("DMS", 26),
+    ("UTC TSF Offset", 27),
+    ("TDLS Peer U-APSD Buffer STA Support", 28),
+    ("TDLS Peer PSM Support", 29),
+    ("TDLS channel switching", 30),
+    ("Interworking", 31),
+    ("QoS Map", 32),
+    ("EBR", 33),
+    ("SSPN Interface", 34),
+    ("MSGCF Capability", 36),
+    ("TDLS Support", 37),
+    ("TDLS Prohibited", 38),
+    ("TDLS Channel Switching Prohibited", 39),
+    ("Reject Unadmitted Frame", 40),
+    ("Identifier Location", 44),
+    ("U-APSD Coexistence", 45),
+    ("WNM-Notification", 46),
+    ("UTF-8 SSID", 48),
+    ("QMFActivated", 49),
+    ("QMFReconfigurationActivated", 50),
+    ("Robust AV Streaming", 51),
+    ("Advanced GCR", 52),
+    ("Mesh GCR", 53),
+    ("SCS", 54),
+    ("QLoad Report", 55),
+    ("Alternate EDCA", 56),
+    ("Unprotected TXOP Negotiation", 57),
+    ("Protected TXOP egotiation", 58),
+    ("Protected QLoad Report", 60),
+    ("TDLS Wider Bandwidth", 61),
+    ("Operating Mode Notification", 62),
+    ("Channel Schedule Management", 65),
+    ("Geodatabase Inband Enabling Signal", 66),
+    ("Network Channel Control", 67),
+    ("White Space Map", 68),
+    ("Channel Availability Query", 69),
+    ("FTM Responder", 70),
+    ("FTM Initiator", 71),
+    ("Extended Spectrum Management Capable", 73),
+    ("TWT Requester Support", 77),
+    ("TWT Responder Support", 78),
+];
+
+// The access categories of the WMM Parameter element in ACI order
+const ACCESS_CATEGORIES: &[&str] = &["BE", "BK", "VI", "VO"];
+
+#[derive(Debug, Default)]
+struct IwBss {
+    bssid: String,
+    sections: Vec<Section>,
+}
+
+// A line at the top level of a BSS entry and the more indented lines that follow it, e.g.
+// "RSN:" and its "* Group cipher: CCMP" lines
+#[derive(Debug, Default)]
+struct Section {
+    key: String,
+    value: String,
+    lines: Vec<String>,
+}
+
+// Parses the output of `iw dev <interface> scan` or `iw dev <interface> scan dump`.
+// iw only prints the raw bytes of vendor IEs and of the IEs it can't decode (with -u), so the
+// IEs it decodes are rebuilt from its text. Fields that iw doesn't print are left zero.
+pub fn parse_scan(text: &str) -> Result<Vec<Bss>, String> {
+    let mut records = Vec::new();
+    let mut current: Option<IwBss> = None;
+    let mut top_level_indent = None;
+
+    for line in text.lines() {
+        if let Some(rest) = line.strip_prefix("BSS ") {
+            records.extend(current.take());
+            current = Some(IwBss {
+                bssid: rest
+                    .split(|c: char| c == '(' || c.is_whitespace())
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+                ..IwBss::default()
+            });
+            top_level_indent = None;
+            continue;
+        }
+
+        let record = match current.as_mut() {
+            Some(record) => record,
+            None => continue,
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        // The first line of an entry sets the indentation of its top level lines
+        let indent = line.len() - line.trim_start().len();
+        let top_level_indent = *top_level_indent.get_or_insert(indent);
+        // Only trim the start, iw escapes trailing spaces in SSIDs
+        let line = strip_bullet(line);
+
+        match record.sections.last_mut() {
+            Some(section) if indent > top_level_indent => section.lines.push(line.to_string()),
+            _ => {
+                let (key, value) = split_key(line);
+                let mut section = Section {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                    lines: Vec::new(),
+                };
+                // RSN, WPA and WMM print their first line after the section name
+                if value.trim_start().starts_with('*') {
+                    section.lines.push(strip_bullet(value).to_string());
+                }
+                record.sections.push(section);
+            }
+        }
+    }
+    records.extend(current);
+
+    if records.is_empty() {
+        return Err("No BSS entries found in the iw output".to_string());
+    }
+
+    records.into_iter().map(IwBss::into_bss).collect()
+}
+
+impl IwBss {
+    fn into_bss(self) -> Result<Bss, String> {
+        let mut frequency_mhz = None;
+        let mut signal_dbm = 0;
+        let mut beacon_interval = 0;
+        let mut capability_info = 0;
+        let mut ies: Vec<(u8, Vec<u8>)> = Vec::new();
+
+        for section in &self.sections {
+            let value = section.value.as_str();
+            let lines = &section.lines;
+            // iw has changed the capitalisation of some names between versions
+            match section.key.to_lowercase().as_str() {
+                "freq" => frequency_mhz = value.trim().parse::<f64>().ok().map(|f| f as u32),
+                "signal" => {
+                    signal_dbm = first_number(value)
+                        .map(|s| s.round() as i32)
+                        .unwrap_or_default()
+                }
+                "beacon interval" => {
+                    beacon_interval = first_number(value).unwrap_or_default() as u16
+                }
+                "capability" => {
+                    capability_info = last_hex(value)
+                        .and_then(|hex| u16::from_str_radix(hex, 16).ok())
+                        .unwrap_or_default()
+                }
+                "ssid" => ies.push((SSID_ID, unescape(value))),
+                "supported rates" => ies.push((SUPPORTED_RATES_ID, rates(value))),
+                "extended supported rates" => ies.push((EXTENDED_SUPPORTED_RATES_ID, rates(value))),
+                "ds parameter set" => {
+                    if let Some(channel) = first_number(value) {
+                        ies.push((DS_PARAMETER_SET_ID, vec![channel as u8]));
+                    }
+                }
+                "country" => ies.push((COUNTRY_ID, country(value, lines))),
+                "bss load" => ies.push((BSS_LOAD_ID, bss_load(lines))),
+                "power constraint" => {
+                    if let Some(constraint) = first_number(value) {
+                        ies.push((POWER_CONSTRAINT_ID, vec![constraint as u8]));
+                    }
+                }
+                "tpc report" => {
+                    if let Some(tx_power) = first_number(value) {
+                        ies.push((TPC_REPORT_ID, vec![tx_power as i8 as u8, 0]));
+                    }
+                }
+                "ht capabilities" => ies.push((HT_CAPABILITIES_ID, ht_capabilities(lines))),
+                "rsn" => {
+                    if let Some(rsn) = rsn(lines, IEEE_OUI) {
+                        ies.push((RSN_ID, rsn));
+                    }
+                }
+                "wpa" => {
+                    if let Some(wpa) = rsn(lines, MICROSOFT_OUI) {
+                        let mut vendor = MICROSOFT_OUI.to_vec();
+                        vendor.push(WPA_OUI_TYPE);
+                        vendor.extend(wpa);
+                        ies.push((VENDOR_SPECIFIC_ID, vendor));
+                    }
+                }
+                "md" => {
+                    if let Some(mobility_domain) = mobility_domain(value, lines) {
+                        ies.push((MOBILITY_DOMAIN_ID, mobility_domain));
+                    }
+                }
+                "ht operation" => ies.push((HT_OPERATION_ID, ht_operation(lines))),
+                "rm enabled capabilities" => ies.push((
+                    RM_ENABLED_CAPABILITIES_ID,
+                    field(lines, "Capabilities")
+                        .map(hex_bytes)
+                        .unwrap_or_else(|| vec![0; 5]),
+                )),
+                "extended capabilities" => {
+                    ies.push((EXTENDED_CAPABILITIES_ID, extended_capabilities(lines)))
+                }
+                "vht capabilities" => ies.push((VHT_CAPABILITIES_ID, vht_capabilities(lines))),
+                "vht operation" => ies.push((VHT_OPERATION_ID, vht_operation(lines))),
+                "he capabilities" => ies.push((
+                    ELEMENT_ID_EXTENSION,
+                    [vec![HE_CAPABILITIES_EXT_ID], he_capabilities(lines)].concat(),
+                )),
+                "he operation" => ies.push((
+                    ELEMENT_ID_EXTENSION,
+                    [vec![HE_OPERATION_EXT_ID], he_operation(lines)].concat(),
+                )),
+                "wmm" => {
+                    if let Some(wmm) = wmm(lines) {
+                        ies.push((VENDOR_SPECIFIC_ID, wmm));
+                    }
+                }
+                "vendor specific" => {
+                    // Vendor specific: OUI 00:50:f2, data: 04 10 4a 00 01 10
+                    let oui = value
+                        .trim_start_matches("OUI ")
+                        .split(',')
+                        .next()
+                        .map(hex_bytes)
+                        .unwrap_or_default();
+                    let data = value
+                        .split("data:")
+                        .nth(1)
+                        .map(hex_bytes)
+                        .unwrap_or_default();
+                    ies.push((VENDOR_SPECIFIC_ID, [oui, data].concat()));
+                }
+                key if key.starts_with("unknown ie (") => {
+                    if let Some(id) = first_number(key) {
+                        ies.push((id as u8, hex_bytes(value)));
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        let frequency_mhz =
+            frequency_mhz.ok_or_else(|| format!("BSS {} has no freq", self.bssid))?;
+        Ok(Bss::new(
+            self.bssid,
+            frequency_mhz,
+            signal_dbm,
+            beacon_interval,
+            capability_info,
+            ies.iter()
+                .map(|(id, bytes)| Ie::from_bytes(*id, bytes))
+                .collect(),
+        ))
+    }
+}
+
+//   * station count: 3
+//   * channel utilisation: 48/255
+//   * available admission capacity: 0 [*32us]
+fn bss_load(lines: &[String]) -> Vec<u8> {
+    let number = |key| field(lines, key).and_then(first_number).unwrap_or_default() as u16;
+    let mut bss_load = number("station count").to_le_bytes().to_vec();
+    bss_load.push(number("channel utilisation") as u8);
+    bss_load.extend(&number("available admission capacity").to_le_bytes());
+    bss_load
+}
+
+// Country: US	Environment: Indoor/Outdoor
+//   Channels [36 - 48] @ 23 dBm
+//   Extension ID: 201 Regulatory Class: 1 Coverage class: 0 (up to 0m)
+fn country(value: &str, lines: &[String]) -> Vec<u8> {
+    let mut country = value
+        .as_bytes()
+        .iter()
+        .take(2)
+        .copied()
+        .collect::<Vec<u8>>();
+    country.resize(2, b' ');
+    country.push(if value.contains("Indoor only") {
+        b'I'
+    } else if value.contains("Outdoor only") {
+        b'O'
+    } else {
+        b' '
+    });
+
+    for line in lines {
+        let numbers = numbers(line);
+        if line.starts_with("Channels") && numbers.len() >= 3 {
+            // Channels are numbered 1 apart in 2.4 GHz and 4 apart elsewhere
+            let spacing = if numbers[0] <= 14.0 { 1.0 } else { 4.0 };
+            let count = (numbers[1] - numbers[0]) / spacing + 1.0;
+            country.extend(&[numbers[0] as u8, count as u8, numbers[2] as i8 as u8]);
+        } else if line.starts_with("Extension ID") && numbers.len() >= 3 {
+            country.extend(&[numbers[0] as u8, numbers[1] as u8, numbers[2] as u8]);
+        }
+    }
+
+    country
+}
+
+//   * Version: 1
+//   * Group cipher: CCMP
+//   * Pairwise ciphers: CCMP TKIP
+//   * Authentication suites: PSK SAE
+//   * Capabilities: 16-PTKSA-RC 1-GTKSA-RC MFP-capable (0x008c)
+//   * Group mgmt cipher suite: AES-128-CMAC
+fn rsn(lines: &[String], oui: [u8; 3]) -> Option<Vec<u8>> {
+    let version = field(lines, "Version").and_then(first_number)? as u16;
+    let mut rsn = version.to_le_bytes().to_vec();
+
+    let group = field(lines, "Group cipher").map(|group| suites(group, CIPHER_SUITES, oui));
+    match group.as_ref().and_then(|group| group.first()) {
+        Some(group) => rsn.extend(group),
+        None => return Some(rsn),
+    }
+
+    for (key, names) in &[
+        ("Pairwise ciphers", CIPHER_SUITES),
+        ("Authentication suites", AKM_SUITES),
+    ] {
+        let list = match field(lines, key) {
+            Some(list) => suites(list, names, oui),
+            None => return Some(rsn),
+        };
+        rsn.extend(&(list.len() as u16).to_le_bytes());
+        rsn.extend(list.concat());
+    }
+
+    let capabilities = field(lines, "Capabilities")
+        .and_then(last_hex)
+        .and_then(|hex| u16::from_str_radix(hex, 16).ok());
+    match capabilities {
+        Some(capabilities) => rsn.extend(&capabilities.to_le_bytes()),
+        None => return Some(rsn),
+    }
+
+    // iw doesn't print PMKIDs from beacons, only their count
+    let group_management = field(lines, "Group mgmt cipher suite")
+        .and_then(|suite| suites(suite, CIPHER_SUITES, oui).into_iter().next());
+    if let Some(group_management) = group_management {
+        rsn.extend(&[0, 0]);
+        rsn.extend(&group_management);
+    }
+
+    Some(rsn)
+}
+
+// Suite names can contain spaces (e.g. "IEEE 802.1X"), so match the longest known name at each
+// position. Suites iw doesn't know are printed as "00-0f-ac:99".
+fn suites(text: &str, names: &[(&str, u8)], oui: [u8; 3]) -> Vec<[u8; 4]> {
+    let mut suites = Vec::new();
+    let mut rest = text.trim();
+
+    while !rest.is_empty() {
+        let known = names
+            .iter()
+            .filter(|(name, _)| {
+                rest.starts_with(name)
+                    && !rest[name.len()..].starts_with(|c: char| !c.is_whitespace())
+            })
+            .max_by_key(|(name, _)| name.len());
+        let token = match known {
+            Some((name, suite_type)) => {
+                suites.push([oui[0], oui[1], oui[2], *suite_type]);
+                &rest[..name.len()]
+            }
+            None => {
+                let token = rest.split_whitespace().next().unwrap_or_default();
+                let mut parts = token.split(':');
+                let suite_oui = parts.next().map(hex_bytes).unwrap_or_default();
+                let suite_type = parts.next().and_then(|suite_type| suite_type.parse().ok());
+                if let (3, Some(suite_type)) = (suite_oui.len(), suite_type) {
+                    suites.push([suite_oui[0], suite_oui[1], suite_oui[2], suite_type]);
+                }
+                token
+            }
+        };
+        rest = rest[token.len()..].trim_start();
+    }
+
+    suites
+}
+
+// MD: MDID: 0x1234 Over-the-DS
+fn mobility_domain(value: &str, lines: &[String]) -> Option<Vec<u8>> {
+    let text = std::iter::once(value)
+        .chain(lines.iter().map(String::as_str))
+        .collect::<Vec<&str>>()
+        .join(" ");
+    let mdid = text
+        .split("0x")
+        .nth(1)
+        .and_then(|hex| u16::from_str_radix(hex.get(0..4)?, 16).ok())?;
+    let mut policy = 0;
+    if text.contains("Over-the-DS") {
+        policy |= 0x01;
+    }
+    if text.contains("Resource-Req") {
+        policy |= 0x02;
+    }
+    Some([mdid.to_le_bytes().to_vec(), vec![policy]].concat())
+}
+
+//   Capabilities: 0x9ef
+//   Maximum RX AMPDU length 65535 bytes (exponent: 0x003)
+//   Minimum RX AMPDU time spacing: 4 usec (0x05)
+//   HT RX MCS rate indexes supported: 0-23
+//   HT TX MCS rate indexes are undefined
+fn ht_capabilities(lines: &[String]) -> Vec<u8> {
+    let mut ht = vec![0; 26];
+    let line_hex = |prefix: &str| {
+        lines
+            .iter()
+            .find(|line| line.starts_with(prefix))
+            .and_then(|line| last_hex(line))
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            .unwrap_or_default()
+    };
+
+    let info = field(lines, "Capabilities")
+        .and_then(|hex| u16::from_str_radix(hex.trim().trim_start_matches("0x"), 16).ok())
+        .unwrap_or_default();
+    ht[0..2].copy_from_slice(&info.to_le_bytes());
+    ht[2] = (line_hex("Maximum RX AMPDU length") & 0x03)
+        | ((line_hex("Minimum RX AMPDU time spacing") & 0x07) << 2);
+
+    let mcs = lines
+        .iter()
+        .find(|line| line.starts_with("HT RX MCS") || line.starts_with("HT TX/RX MCS"))
+        .and_then(|line| line.split(':').nth(1));
+    for index in mcs.map(index_list).unwrap_or_default() {
+        if index < 77 {
+            ht[3 + index / 8] |= 1 << (index % 8);
+        }
+    }
+    if let Some(highest) = field(lines, "HT RX highest supported").and_then(first_number) {
+        ht[13..15].copy_from_slice(&(highest as u16).to_le_bytes());
+    }
+    if !lines
+        .iter()
+        .any(|line| line.contains("TX MCS rate indexes are undefined"))
+    {
+        ht[15] = 0x01;
+    }
+
+    ht
+}
+
+//   * primary channel: 36
+//   * secondary channel offset: above
+//   * STA channel width: any
+//   * RIFS: 0
+//   * HT protection: no
+//   * non-GF present: 1
+//   * OBSS non-GF present: 0
+//   * dual beacon: 0
+//   * dual CTS protection: 0
+//   * STBC beacon: 0
+fn ht_operation(lines: &[String]) -> Vec<u8> {
+    let mut ht = vec![0; 22];
+    let flag = |key| field(lines, key).and_then(first_number).unwrap_or_default() != 0.0;
+
+    ht[0] = field(lines, "primary channel")
+        .and_then(first_number)
+        .unwrap_or_default() as u8;
+    ht[1] = match field(lines, "secondary channel offset").map(str::trim) {
+        Some("above") => 1,
+        Some("below") => 3,
+        _ => 0,
+    };
+    if field(lines, "STA channel width").map(str::trim) == Some("any") {
+        ht[1] |= 0x04;
+    }
+    if flag("RIFS") {
+        ht[1] |= 0x08;
+    }
+    ht[2] = match field(lines, "HT protection").map(str::trim) {
+        Some("nonmember") => 1,
+        Some("20 MHz") => 2,
+        Some("non-HT mixed") => 3,
+        _ => 0,
+    };
+    if flag("non-GF present") {
+        ht[2] |= 0x04;
+    }
+    if flag("OBSS non-GF present") {
+        ht[2] |= 0x10;
+    }
+    if flag("dual beacon") {
+        ht[4] |= 0x40;
+    }
+    if flag("dual CTS protection") {
+        ht[4] |= 0x80;
+    }
+    if flag("STBC beacon") {
+        ht[5] |= 0x01;
+    }
+
+    ht
+}
+
+//   * Extended Channel Switching
+//   * BSS Transition
+//   * UTF-8 SSID
+fn extended_capabilities(lines: &[String]) -> Vec<u8> {
+    let bits = lines
+        .iter()
+        .filter_map(|line| {
+            EXTENDED_CAPABILITIES
+                .iter()
+                .find(|(name, _)| line.trim() == *name)
+                .map(|(_, bit)| *bit)
+        })
+        .collect::<Vec<usize>>();
+
+    let mut capabilities = vec![0; bits.iter().max().map_or(0, |bit| bit / 8 + 1)];
+    for bit in bits {
+        capabilities[bit / 8] |= 1 << (bit % 8);
+    }
+    capabilities
+}
+
+//   VHT Capabilities (0x0f8b69b2):
+//   ...
+//   VHT RX MCS set:
+//     1 streams: MCS 0-9
+//     2 streams: not supported
+//   VHT RX highest supported: 0 Mbps
+//   VHT TX MCS set:
+//   ...
+fn vht_capabilities(lines: &[String]) -> Vec<u8> {
+    let info = lines
+        .iter()
+        .find(|line| line.starts_with("VHT Capabilities"))
+        .and_then(|line| last_hex(line))
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+        .unwrap_or_default();
+    let maps = mcs_maps(lines, &["VHT RX MCS set", "VHT TX MCS set"], VHT_MCS);
+    let highest = |key| field(lines, key).and_then(first_number).unwrap_or_default() as u16;
+
+    [
+        info.to_le_bytes().to_vec(),
+        maps[0].to_le_bytes().to_vec(),
+        highest("VHT RX highest supported").to_le_bytes().to_vec(),
+        maps[1].to_le_bytes().to_vec(),
+        highest("VHT TX highest supported").to_le_bytes().to_vec(),
+    ]
+    .concat()
+}
+
+//   * channel width: 1 (80 MHz)
+//   * center freq segment 1: 42
+//   * center freq segment 2: 0
+//   * VHT basic MCS set: 0xfffc
+fn vht_operation(lines: &[String]) -> Vec<u8> {
+    let number = |key| field(lines, key).and_then(first_number).unwrap_or_default() as u8;
+    let basic_mcs = field(lines, "VHT basic MCS set")
+        .and_then(|hex| u16::from_str_radix(hex.trim().trim_start_matches("0x"), 16).ok())
+        .unwrap_or(0xffff);
+    [
+        vec![
+            number("channel width"),
+            number("center freq segment 1"),
+            number("center freq segment 2"),
+        ],
+        basic_mcs.to_le_bytes().to_vec(),
+    ]
+    .concat()
+}
+
+//   HE MAC Capabilities (0x000801185018):
+//   ...
+//   HE PHY Capabilities: (0x0c3f0e09fd098008ffffff):
+//   ...
+//   HE RX MCS and NSS set <= 80 MHz
+//     1 streams: MCS 0-11
+//   HE TX MCS and NSS set <= 80 MHz
+//   ...
+fn he_capabilities(lines: &[String]) -> Vec<u8> {
+    let hex = |prefix: &str| {
+        lines
+            .iter()
+            .find(|line| line.starts_with(prefix))
+            .and_then(|line| last_hex(line))
+            .unwrap_or_default()
+    };
+
+    // The MAC capabilities are printed as three little endian 16 bit words, the PHY
+    // capabilities as bytes in order
+    let mut he = (0..3)
+        .flat_map(|i| {
+            hex("HE MAC Capabilities")
+                .get(i * 4..i * 4 + 4)
+                .and_then(|word| u16::from_str_radix(word, 16).ok())
+                .unwrap_or_default()
+                .to_le_bytes()
+                .to_vec()
+        })
+        .collect::<Vec<u8>>();
+    let mut phy = hex_digits(hex("HE PHY Capabilities"));
+    phy.resize(11, 0);
+    he.extend(phy);
+
+    for width in &["<= 80 MHz", "160 MHz", "80+80 MHz"] {
+        let rx = format!("HE RX MCS and NSS set {}", width);
+        let tx = format!("HE TX MCS and NSS set {}", width);
+        if lines.iter().any(|line| line.starts_with(&rx)) {
+            let maps = mcs_maps(lines, &[rx.as_str(), tx.as_str()], HE_MCS);
+            he.extend(&maps[0].to_le_bytes());
+            he.extend(&maps[1].to_le_bytes());
+        }
+    }
+
+    he
+}
+
+//   HE Operation Parameters: (0x003ff4)
+//   ...
+//   BSS Color: 26
+//   Basic HE-MCS NSS Set: 0xfffc
+//   VHT Operation Information: ...
+//   6 GHz Operation Information
+//     Primary Channel: 5
+//     Channel Width: 80 MHz
+//     Center Frequency Segment 0: 7
+//     Center Frequency Segment 1: 0
+//     Minimum Rate: 6
+fn he_operation(lines: &[String]) -> Vec<u8> {
+    let parameters = lines
+        .iter()
+        .find(|line| line.starts_with("HE Operation Parameters"))
+        .and_then(|line| last_hex(line))
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+        .unwrap_or_default();
+    let mut he = parameters.to_le_bytes()[0..3].to_vec();
+
+    let mut color = field(lines, "BSS Color")
+        .and_then(first_number)
+        .unwrap_or_default() as u8;
+    if lines
+        .iter()
+        .any(|line| line.starts_with("Partial BSS Color"))
+    {
+        color |= 0x40;
+    }
+    if lines
+        .iter()
+        .any(|line| line.starts_with("BSS Color Disabled"))
+    {
+        color |= 0x80;
+    }
+    he.push(color);
+    let basic_mcs = lines
+        .iter()
+        .find(|line| line.starts_with("Basic HE-MCS"))
+        .and_then(|line| line.split("0x").nth(1))
+        .and_then(|hex| u16::from_str_radix(hex.trim(), 16).ok())
+        .unwrap_or(0xffff);
+    he.extend(&basic_mcs.to_le_bytes());
+
+    // The optional fields repeat field names, so look for them in their own part of the lines
+    let six_ghz_start = lines
+        .iter()
+        .position(|line| line.starts_with("6 GHz Operation Information"))
+        .unwrap_or(lines.len());
+    let (lines, six_ghz) = lines.split_at(six_ghz_start);
+    let number = |lines: &[String], key: &str| {
+        lines
+            .iter()
+            .find(|line| line.contains(key))
+            .and_then(|line| line.split(':').nth(1))
+            .and_then(first_number)
+            .unwrap_or_default() as u8
+    };
+
+    if parameters & 0x00_4000 != 0 {
+        he.extend(&[
+            number(lines, "Channel Width"),
+            number(lines, "Segment 0"),
+            number(lines, "Segment 1"),
+        ]);
+    }
+    if parameters & 0x00_8000 != 0 {
+        he.push(number(lines, "BSSID Indicator"));
+    }
+    if parameters & 0x02_0000 != 0 {
+        let width = match field(six_ghz, "Channel Width").map(str::trim) {
+            Some("20 MHz") => 0,
+            Some("40 MHz") => 1,
+            Some("80 MHz") => 2,
+            _ => 3,
+        };
+        he.extend(&[
+            number(six_ghz, "Primary Channel"),
+            width,
+            number(six_ghz, "Segment 0"),
+            number(six_ghz, "Segment 1"),
+            number(six_ghz, "Minimum Rate"),
+        ]);
+    }
+
+    he
+}
+
+//   * Parameter version 1
+//   * u-APSD
+//   * BE: CW 15-1023, AIFSN 3
+//   * VI: CW 7-15, AIFSN 2, TXOP 3008 usec
+fn wmm(lines: &[String]) -> Option<Vec<u8>> {
+    let version = lines
+        .iter()
+        .find(|line| line.starts_with("Parameter version"))
+        .and_then(|line| first_number(line))? as u8;
+    let qos_info = if lines.iter().any(|line| line.trim() == "u-APSD") {
+        0x80
+    } else {
+        0
+    };
+    let mut wmm = [
+        MICROSOFT_OUI.to_vec(),
+        vec![WMM_OUI_TYPE, 1, version, qos_info, 0],
+    ]
+    .concat();
+
+    for (aci, access_category) in ACCESS_CATEGORIES.iter().enumerate() {
+        let parameters = field(lines, access_category).unwrap_or_default();
+        let numbers = numbers(parameters);
+        let (cw_min, cw_max, aifsn) = match numbers.as_slice() {
+            [cw_min, cw_max, aifsn, ..] => (*cw_min as u32, *cw_max as u32, *aifsn as u8),
+            _ => (15, 1023, 2),
+        };
+        let txop_us = if parameters.contains("TXOP") {
+            numbers.last().copied().unwrap_or_default() as u16
+        } else {
+            0
+        };
+        let acm = if parameters.contains("acm") { 0x10 } else { 0 };
+        // The contention windows are sent as exponents, CW = 2^ECW - 1
+        let ecw = |cw: u32| (32 - (cw + 1).leading_zeros() - 1) as u8;
+
+        wmm.push(aifsn | acm | (aci as u8) << 5);
+        wmm.push(ecw(cw_min) | ecw(cw_max) << 4);
+        wmm.extend(&(txop_us / 32).to_le_bytes());
+    }
+
+    Some(wmm)
+}
+
+// The highest MCS each value of a 2 bit Max MCS For n SS subfield allows
+const VHT_MCS: [&str; 3] = ["MCS 0-7", "MCS 0-8", "MCS 0-9"];
+const HE_MCS: [&str; 3] = ["MCS 0-7", "MCS 0-9", "MCS 0-11"];
+
+// Rebuilds the MCS maps from the "n streams: MCS 0-9" lines under each of the headers
+fn mcs_maps(lines: &[String], headers: &[&str], mcs: [&str; 3]) -> Vec<u16> {
+    headers
+        .iter()
+        .map(|header| {
+            let streams = lines
+                .iter()
+                .skip_while(|line| !line.starts_with(header))
+                .skip(1)
+                .take_while(|line| line.contains("streams:"));
+            // Streams that aren't listed aren't supported
+            let mut map = 0xffff;
+            for line in streams {
+                let stream = first_number(line).unwrap_or_default() as u16;
+                let value = mcs
+                    .iter()
+                    .position(|mcs| line.trim_end().ends_with(mcs))
+                    .unwrap_or(3) as u16;
+                if (1..=8).contains(&stream) {
+                    let shift = (stream - 1) * 2;
+                    map = (map & !(0x03 << shift)) | (value << shift);
+                }
+            }
+            map
+        })
+        .collect()
+}
+
+// The value of the first line with the given key, matching the key case insensitively
+fn field<'a>(lines: &'a [String], key: &str) -> Option<&'a str> {
+    lines.iter().find_map(|line| {
+        let (line_key, value) = split_key(line);
+        if line_key.eq_ignore_ascii_case(key) {
+            Some(value)
+        } else {
+            None
+        }
+    })
+}
+
+fn split_key(line: &str) -> (&str, &str) {
+    match line.find(':') {
+        Some(colon) => (&line[..colon], line[colon + 1..].trim_start()),
+        None => (line.trim_end(), ""),
+    }
+}
+
+fn strip_bullet(line: &str) -> &str {
+    line.trim_start().trim_start_matches("* ")
+}
+
+// The hex digits after the last "0x" in the text, e.g. "0411" in "ESS Privacy (0x0411)"
+fn last_hex(text: &str) -> Option<&str> {
+    let start = text.rfind("0x")? + 2;
+    let hex = &text[start..];
+    let end = hex
+        .find(|c: char| !c.is_ascii_hexdigit())
+        .unwrap_or(hex.len());
+    Some(&hex[..end])
+}
+
+// Decodes a run of hex digits, e.g. "0c3f0e", two digits per byte
+fn hex_digits(hex: &str) -> Vec<u8> {
+    (0..hex.len() / 2)
+        .filter_map(|i| u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok())
+        .collect()
+}
+
+// MCS indexes as iw lists them, e.g. "0-15, 32"
+fn index_list(text: &str) -> Vec<usize> {
+    text.split(',')
+        .flat_map(|range| {
+            let bounds = range
+                .split('-')
+                .filter_map(|bound| bound.trim().parse::<usize>().ok())
+                .collect::<Vec<usize>>();
+            match bounds.as_slice() {
+                [first, last] => (*first..=*last).collect(),
+                [index] => vec![*index],
+                _ => Vec::new(),
+            }
+        })
+        .collect()
+}
+
+// Rates are printed in Mbps with a * marking basic rates, e.g. "1.0* 2.0* 5.5 11.0"
+fn rates(value: &str) -> Vec<u8> {
+    value
+        .split_whitespace()
+        .filter_map(|rate| {
+            let basic = rate.ends_with('*');
+            rate.trim_end_matches('*')
+                .parse::<f64>()
+                .ok()
+                .map(|mbps| (mbps * 2.0) as u8 | if basic { 0x80 } else { 0 })
+        })
+        .collect()
+}
+
+fn first_number(text: &str) -> Option<f64> {
+    numbers(text).into_iter().next()
+}
+
+// Numbers in the text, splitting ranges such as "15-1023" but keeping signs such as "-45.00"
+fn numbers(text: &str) -> Vec<f64> {
+    text.split(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .flat_map(|token| match token.parse::<f64>() {
+            Ok(number) => vec![number],
+            Err(_) => token
+                .split('-')
+                .filter_map(|part| part.parse().ok())
+                .collect(),
+        })
+        .collect()
+}
+
+fn hex_bytes(text: &str) -> Vec<u8> {
+    text.split(|c: char| c.is_whitespace() || c == ':' || c == '-')
+        .filter_map(|byte| u8::from_str_radix(byte.trim_start_matches("0x"), 16).ok())
+        .collect()
+}
+
+// iw escapes non-printable SSID bytes as \xNN and spaces at the ends as "\ "
+fn unescape(ssid: &str) -> Vec<u8> {
+    let bytes = ssid.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'\\' && bytes.get(i + 1) == Some(&b'x') {
+            if let Some(byte) = ssid
+                .get(i + 2..i + 4)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                unescaped.push(byte);
+                i += 4;
+                continue;
+            }
+        } else if bytes[i] == b'\\' && bytes.get(i + 1) == Some(&b' ') {
+            unescaped.push(b' ');
+            i += 2;
+            continue;
+        }
+        unescaped.push(bytes[i]);
+        i += 1;
+    }
+
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::{self, capabilities, BssLoad, Country, Rsn, WmmParameters};
+    use kawaiifi::InformationElement;
+
+    const WPA2_SCAN: &str = "BSS 3c:84:6a:12:34:56(on wlp3s0) -- associated
+\tlast seen: 8766.418s [boottime]
+\tTSF: 3264521357 usec (0d, 00:54:24)
+\tfreq: 5180
+\tbeacon interval: 100 TUs
+\tcapability: ESS Privacy SpectrumMgmt RadioMeasure (0x1111)
+\tsignal: -58.00 dBm
+\tlast seen: 132 ms ago
+\tInformation elements from Probe Response frame:
+\tSSID: Home\\x00Net\\ 
+\tSupported rates: 6.0* 9.0 12.0* 18.0 24.0* 36.0 48.0 54.0 
+\tDS Parameter set: channel 36
+\tCountry: US\tEnvironment: Indoor/Outdoor
+\t\tChannels [36 - 48] @ 23 dBm
+\t\tChannels [149 - 165] @ 30 dBm
+\tPower constraint: 3 dB
+\tTPC report: TX power: 17 dBm
+\tBSS Load:
+\t\t * station count: 4
+\t\t * channel utilisation: 31/255
+\t\t * available admission capacity: 0 [*32us]
+\tHT capabilities:
+\t\tCapabilities: 0x9ef
+\t\t\tRX LDPC
+\t\t\tHT20/HT40
+\t\t\tSM Power Save disabled
+\t\t\tMax AMSDU length: 7935 bytes
+\t\tMaximum RX AMPDU length 65535 bytes (exponent: 0x003)
+\t\tMinimum RX AMPDU time spacing: 4 usec (0x05)
+\t\tHT RX MCS rate indexes supported: 0-23
+\t\tHT TX MCS rate indexes are undefined
+\tHT operation:
+\t\t * primary channel: 36
+\t\t * secondary channel offset: above
+\t\t * STA channel width: any
+\t\t * RIFS: 0
+\t\t * HT protection: no
+\t\t * non-GF present: 1
+\t\t * OBSS non-GF present: 0
+\t\t * dual beacon: 0
+\t\t * dual CTS protection: 0
+\t\t * STBC beacon: 0
+\tRSN:\t * Version: 1
+\t\t * Group cipher: CCMP
+\t\t * Pairwise ciphers: CCMP
+\t\t * Authentication suites: PSK
+\t\t * Capabilities: 16-PTKSA-RC 1-GTKSA-RC (0x000c)
+\tRM enabled capabilities:
+\t\tCapabilities: 0x73 0xd0 0x00 0x00 0x0c
+\t\t\tLink Measurement
+\t\t\tNeighbor Report
+\t\tNonoperating Channel Max Measurement Duration: 0
+\tExtended capabilities:
+\t\t * Extended Channel Switching
+\t\t * BSS Transition
+\t\t * Operating Mode Notification
+\tVHT capabilities:
+\t\tVHT Capabilities (0x0f8b69b2):
+\t\t\tMax MPDU length: 11454
+\t\tVHT RX MCS set:
+\t\t\t1 streams: MCS 0-9
+\t\t\t2 streams: MCS 0-9
+\t\t\t3 streams: not supported
+\t\tVHT RX highest supported: 0 Mbps
+\t\tVHT TX MCS set:
+\t\t\t1 streams: MCS 0-8
+\t\tVHT TX highest supported: 0 Mbps
+\tVHT operation:
+\t\t * channel width: 1 (80 MHz)
+\t\t * center freq segment 1: 42
+\t\t * center freq segment 2: 0
+\t\t * VHT basic MCS set: 0xfffc
+\tWMM:\t * Parameter version 1
+\t\t * u-APSD
+\t\t * BE: CW 15-1023, AIFSN 3
+\t\t * BK: CW 15-1023, AIFSN 7
+\t\t * VI: CW 7-15, AIFSN 2, TXOP 3008 usec
+\t\t * VO: CW 3-7, AIFSN 2, acm, TXOP 1504 usec
+\tVendor specific: OUI 00:50:f2, data: 04 10 4a 00 01 10
+\tUnknown IE (42): 00
+BSS aa:bb:cc:dd:ee:ff(on wlp3s0)
+\tfreq: 2412
+\tSSID: Cafe
+\tWPA:\t * Version: 1
+\t\t * Group cipher: TKIP
+\t\t * Pairwise ciphers: TKIP CCMP
+\t\t * Authentication suites: IEEE 802.1X FT/IEEE 802.1X 00-0f-ac:99
+";
+
+    fn ie(bss: &Bss, id: u8) -> &[u8] {
+        decode::find_ie(bss.ies(), id).unwrap()
+    }
+
+    #[test]
+    fn parses_every_bss() {
+        let scan = parse_scan(WPA2_SCAN).unwrap();
+        assert_eq!(scan.len(), 2);
+        assert_eq!(scan[0].bssid().to_string(), "3c:84:6a:12:34:56");
+        assert!(parse_scan("").is_err());
+        assert!(parse_scan("BSS 00:11:22:33:44:55(on wlan0)\n\tSSID: x\n").is_err());
+    }
+
+    #[test]
+    fn rebuilds_basic_ies() {
+        let bss = &parse_scan(WPA2_SCAN).unwrap()[0];
+        assert_eq!(ie(bss, SSID_ID), b"Home\x00Net ");
+        assert_eq!(
+            ie(bss, SUPPORTED_RATES_ID),
+            &[0x8c, 0x12, 0x98, 0x24, 0xb0, 0x48, 0x60, 0x6c]
+        );
+        assert_eq!(ie(bss, DS_PARAMETER_SET_ID), &[36]);
+        assert_eq!(ie(bss, POWER_CONSTRAINT_ID), &[3]);
+        assert_eq!(ie(bss, TPC_REPORT_ID), &[17, 0]);
+        assert_eq!(ie(bss, 42), &[0]);
+        assert_eq!(
+            decode::find_vendor_ie(bss.ies(), MICROSOFT_OUI, 4),
+            Some(&[0x10, 0x4a, 0x00, 0x01, 0x10][..])
+        );
+
+        let bss_load = BssLoad::from_ies(bss.ies()).unwrap();
+        assert_eq!(bss_load.station_count, Some(4));
+        assert_eq!(bss_load.channel_utilization, Some(31));
+
+        let country = Country::from_ies(bss.ies()).unwrap();
+        assert_eq!(country.code, "US");
+        assert_eq!(country.subbands.len(), 2);
+        assert_eq!(country.subbands[0].channel_count, 4);
+        assert_eq!(country.subbands[1].max_tx_power_dbm, 30);
+    }
+
+    #[test]
+    fn rebuilds_rsn() {
+        let bss = &parse_scan(WPA2_SCAN).unwrap()[0];
+        assert_eq!(
+            ie(bss, RSN_ID),
+            &[1, 0, 0, 0x0f, 0xac, 4, 1, 0, 0, 0x0f, 0xac, 4, 1, 0, 0, 0x0f, 0xac, 2, 0x0c, 0]
+        );
+        let rsn = Rsn::from_ies(bss.ies()).unwrap();
+        assert!(rsn.is_psk());
+        assert!(!rsn.mfp_capable());
+    }
+
+    #[test]
+    fn rebuilds_wpa_with_multi_word_and_unknown_suites() {
+        let bss = &parse_scan(WPA2_SCAN).unwrap()[1];
+        let wpa = Rsn::wpa_from_ies(bss.ies()).unwrap();
+        assert_eq!(wpa.group_cipher, Some([0x00, 0x50, 0xf2, 2]));
+        assert_eq!(wpa.pairwise_ciphers.len(), 2);
+        assert_eq!(
+            wpa.akm_suites,
+            vec![
+                [0x00, 0x50, 0xf2, 1],
+                [0x00, 0x50, 0xf2, 3],
+                [0x00, 0x0f, 0xac, 99]
+            ]
+        );
+        assert!(Rsn::from_ies(bss.ies()).is_none());
+    }
+
+    #[test]
+    fn rebuilds_phy_ies() {
+        let bss = &parse_scan(WPA2_SCAN).unwrap()[0];
+        let ht_capabilities = ie(bss, HT_CAPABILITIES_ID);
+        assert_eq!(ht_capabilities.len(), 26);
+        assert_eq!(
+            &ht_capabilities[0..6],
+            &[0xef, 0x09, 0x17, 0xff, 0xff, 0xff]
+        );
+        assert_eq!(ht_capabilities[15], 0);
+
+        let ht_operation = ie(bss, HT_OPERATION_ID);
+        assert_eq!(ht_operation.len(), 22);
+        assert_eq!(&ht_operation[0..3], &[36, 0x05, 0x04]);
+
+        assert_eq!(
+            ie(bss, VHT_CAPABILITIES_ID),
+            &[0xb2, 0x69, 0x8b, 0x0f, 0xfa, 0xff, 0, 0, 0xfd, 0xff, 0, 0]
+        );
+        assert_eq!(ie(bss, VHT_OPERATION_ID), &[1, 42, 0, 0xfc, 0xff]);
+    }
+
+    #[test]
+    fn rebuilds_capability_ies() {
+        let bss = &parse_scan(WPA2_SCAN).unwrap()[0];
+        assert!(capabilities::extended_capability(bss.ies(), 19));
+        assert!(capabilities::extended_capability(bss.ies(), 62));
+        assert!(!capabilities::extended_capability(bss.ies(), 48));
+        assert_eq!(
+            ie(bss, RM_ENABLED_CAPABILITIES_ID),
+            &[0x73, 0xd0, 0x00, 0x00, 0x0c]
+        );
+        assert!(capabilities::neighbor_report(bss.ies()));
+    }
+
+    #[test]
+    fn rebuilds_wmm() {
+        let bss = &parse_scan(WPA2_SCAN).unwrap()[0];
+        let wmm = WmmParameters::from_ies(bss.ies()).unwrap();
+        assert!(wmm.uapsd());
+        let vo = &wmm.ac_parameters[3];
+        assert_eq!((vo.cw_min, vo.cw_max, vo.aifsn), (3, 7, 2));
+        assert!(vo.acm);
+        assert_eq!(vo.txop_limit_us, 1504);
+        assert_eq!(wmm.ac_parameters[1].aifsn, 7);
+    }
+
+    #[test]
+    fn rebuilds_he_operation_with_6_ghz_information() {
+        let text = "BSS 00:11:22:33:44:55(on wlan0)
+\tfreq: 5975
+\tHE Operation:
+\t\tHE Operation Parameters: (0x023ff4)
+\t\t\tDefault PE Duration: 4
+\t\t\tTXOP Duration RTS Threshold: 1023
+\t\tBSS Color: 26
+\t\tBasic HE-MCS NSS Set: 0xfffc
+\t\t6 GHz Operation Information
+\t\t\tPrimary Channel: 5
+\t\t\tChannel Width: 80 MHz
+\t\t\tCenter Frequency Segment 0: 7
+\t\t\tCenter Frequency Segment 1: 0
+\t\t\tMinimum Rate: 6
+";
+        let bss = &parse_scan(text).unwrap()[0];
+        assert_eq!(
+            decode::find_ext_ie(bss.ies(), HE_OPERATION_EXT_ID),
+            Some(&[0xf4, 0x3f, 0x02, 26, 0xfc, 0xff, 5, 2, 7, 0, 6][..])
+        );
+        assert!(bss.ies().iter().all(|ie| ie.id() == ELEMENT_ID_EXTENSION));
+    }
+}
@@ -1,25 +1,37 @@
+mod analysis;
 mod app;
+mod cli;
+mod commands;
+mod decode;
+mod iw;
+mod terminal;
 mod widgets;
 
 use app::App;
-use crossterm::{
-    event::{read, DisableMouseCapture, Event, KeyCode},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
+use cli::{Command, OutputFormat, ScanOptions, ScanSource};
+use crossterm::event::{read, Event, KeyCode};
 use kawaiifi::{self, Bss};
 use std::{
     collections::HashSet,
-    io::{self, Write},
+    env,
+    io::{self, Stdout},
     iter::Iterator,
-    sync::mpsc::{self, Receiver, TryRecvError},
+    process,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, TryRecvError},
+        Arc,
+    },
     thread::{self, JoinHandle},
     time::Duration,
 };
 use tui::{backend::CrosstermBackend, Terminal};
 use widgets::{BssTableColumnHeader, TableSortOrder};
 
-fn start_scan_thread() -> (Receiver<HashSet<Bss>>, JoinHandle<()>) {
+// Scans back to back instead of once a second while fast_scan is set. kawaiifi only offers a
+// full scan of every channel, so even back to back a scan can't be restricted to the frequency
+// of the BSS being located and each result takes as long as the driver's full scan.
+fn start_scan_thread(fast_scan: Arc<AtomicBool>) -> (Receiver<HashSet<Bss>>, JoinHandle<()>) {
     let (scan_result_tx, scan_result_rx) = mpsc::channel();
     (
         scan_result_rx,
@@ -35,10 +47,31 @@ fn start_scan_thread() -> (Receiver<HashSet<Bss>>, JoinHandle<()>) {
             }
 
             loop {
-                if let Ok(scan_results) = first_interface.scan() {
-                    scan_result_tx.send(scan_results).unwrap();
+                match first_interface.scan() {
+                    Ok(scan_results) => scan_result_tx.send(scan_results).unwrap(),
+                    // Back off while the interface is busy instead of retrying in a tight loop
+                    Err(_) => thread::sleep(Duration::from_millis(250)),
+                }
+                if !fast_scan.load(Ordering::Relaxed) {
+                    thread::sleep(Duration::from_secs(1));
                 }
-                thread::sleep(Duration::from_secs(1));
+            }
+        }),
+    )
+}
+
+fn start_import_thread(scan_results: Vec<Bss>) -> (Receiver<HashSet<Bss>>, JoinHandle<()>) {
+    let (scan_result_tx, scan_result_rx) = mpsc::channel();
+    (
+        scan_result_rx,
+        thread::spawn(move || {
+            scan_result_tx
+                .send(scan_results.into_iter().collect())
+                .unwrap();
+
+            // Imported scan results never change, but keep the channel open until the app exits
+            loop {
+                thread::park();
             }
         }),
     )
@@ -61,24 +94,63 @@ fn start_input_event_thread() -> (Receiver<Event>, JoinHandle<()>) {
 }
 
 fn main() -> Result<(), io::Error> {
+    match Command::from_args(env::args().skip(1)) {
+        Ok(Command::Scan(options)) => match options.format {
+            OutputFormat::Tui => scan(options),
+            _ => commands::scan::run_headless(options),
+        },
+        Ok(Command::Decode(options)) => commands::decode::run(options),
+        Ok(Command::Audit(options)) => {
+            if commands::audit::run(options)? {
+                process::exit(3);
+            }
+            Ok(())
+        }
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, cli::USAGE);
+            process::exit(2);
+        }
+    }
+}
+
+fn scan(options: ScanOptions) -> Result<(), io::Error> {
     let mut app = App::new();
+    if options.show_features {
+        app.toggle_feature_columns();
+    }
+    app.set_path_loss_model(options.path_loss_model);
+    if let Some((allowlist, alert_log)) = commands::scan::load_allowlist(&options)? {
+        app.set_allowlist(allowlist, alert_log);
+    }
 
-    let (scan_rx, scan_thread) = start_scan_thread();
+    let fast_scan = Arc::new(AtomicBool::new(false));
+    let (scan_rx, scan_thread) = match options.source {
+        ScanSource::Interface => start_scan_thread(fast_scan.clone()),
+        ScanSource::IwScan(path) => start_import_thread(commands::scan::import_iw(&path)?),
+    };
 
     let (input_event_rx, input_event_thread) = start_input_event_thread();
 
-    execute!(io::stdout(), EnterAlternateScreen).unwrap();
-    enable_raw_mode().unwrap();
+    // The terminal is restored however the event loop ends, errors included
+    let terminal = terminal::enter()?;
+    let result = run_event_loop(app, terminal, scan_rx, input_event_rx, fast_scan);
+    terminal::leave();
 
-    let backend = CrosstermBackend::new(io::stdout());
-    let mut terminal = Terminal::new(backend)?;
-    terminal.clear()?;
+    result
+}
 
+fn run_event_loop(
+    mut app: App,
+    mut terminal: Terminal<CrosstermBackend<Stdout>>,
+    scan_rx: Receiver<HashSet<Bss>>,
+    input_event_rx: Receiver<Event>,
+    fast_scan: Arc<AtomicBool>,
+) -> Result<(), io::Error> {
     loop {
         // First check for a new scan
         match scan_rx.try_recv() {
             Ok(scan_results) => {
-                app.update_scan_results(scan_results.into_iter().collect::<Vec<Bss>>());
+                app.update_scan_results(scan_results.into_iter().collect::<Vec<Bss>>())?;
                 app.render(&mut terminal)?
             }
             Err(TryRecvError::Empty) => (),
@@ -88,6 +160,28 @@ fn main() -> Result<(), io::Error> {
         // Then check for any input events
         match input_event_rx.try_recv() {
             Ok(event) => match event {
+                // While a search is being typed, keys go to the search
+                Event::Key(key_event) if app.is_searching() => {
+                    match key_event.code {
+                        KeyCode::Char(c) => app.push_search_char(c),
+                        KeyCode::Backspace => app.pop_search_char(),
+                        KeyCode::Enter => app.apply_search(),
+                        KeyCode::Esc => app.cancel_search(),
+                        _ => (),
+                    }
+                    app.render(&mut terminal)?;
+                }
+                // While locating a BSS, only the locate keys apply
+                Event::Key(key_event) if app.is_locating() => {
+                    match key_event.code {
+                        KeyCode::Char('q') => break,
+                        KeyCode::Esc | KeyCode::Char('x') => app.toggle_locate(),
+                        KeyCode::Char('b') => app.toggle_locate_bell(),
+                        KeyCode::Char('p') => app.reset_locate_peak(),
+                        _ => (),
+                    }
+                    app.render(&mut terminal)?;
+                }
                 Event::Key(key_event) => match key_event.code {
                     KeyCode::Char('q') => break,
                     KeyCode::Char('b') => {
@@ -98,6 +192,95 @@ fn main() -> Result<(), io::Error> {
                         app.sort_bss_table(BssTableColumnHeader::Ssid, TableSortOrder::Descending);
                         app.render(&mut terminal)?;
                     }
+                    KeyCode::Char('i') => {
+                        app.sort_bss_table(
+                            BssTableColumnHeader::Interference,
+                            TableSortOrder::Ascending,
+                        );
+                        app.render(&mut terminal)?;
+                    }
+                    KeyCode::Char('n') => {
+                        app.sort_bss_table(
+                            BssTableColumnHeader::Stations,
+                            TableSortOrder::Ascending,
+                        );
+                        app.render(&mut terminal)?;
+                    }
+                    KeyCode::Char('u') => {
+                        app.sort_bss_table(
+                            BssTableColumnHeader::Utilization,
+                            TableSortOrder::Ascending,
+                        );
+                        app.render(&mut terminal)?;
+                    }
+                    KeyCode::Char('o') => {
+                        app.toggle_interference_popup();
+                        app.render(&mut terminal)?;
+                    }
+                    KeyCode::Char(' ') => {
+                        app.toggle_selected_field();
+                        app.render(&mut terminal)?;
+                    }
+                    KeyCode::Char('/') => {
+                        app.start_search();
+                        app.render(&mut terminal)?;
+                    }
+                    KeyCode::Char('a') => {
+                        app.toggle_findings();
+                        app.render(&mut terminal)?;
+                    }
+                    KeyCode::Char('p') => {
+                        app.toggle_lint();
+                        app.render(&mut terminal)?;
+                    }
+                    KeyCode::Char('f') => {
+                        app.toggle_feature_columns();
+                        app.render(&mut terminal)?;
+                    }
+                    KeyCode::Char('t') => {
+                        app.toggle_channel_summary();
+                        app.render(&mut terminal)?;
+                    }
+                    KeyCode::Char('w') => {
+                        app.toggle_edca();
+                        app.render(&mut terminal)?;
+                    }
+                    KeyCode::Char('r') => {
+                        app.toggle_regulatory();
+                        app.render(&mut terminal)?;
+                    }
+                    KeyCode::Char('d') => {
+                        app.toggle_discovery();
+                        app.render(&mut terminal)?;
+                    }
+                    KeyCode::Char('h') => {
+                        app.toggle_hidden_only();
+                        app.render(&mut terminal)?;
+                    }
+                    KeyCode::Char('g') => {
+                        app.toggle_mld_grouping();
+                        app.render(&mut terminal)?;
+                    }
+                    KeyCode::Char('e') => {
+                        app.toggle_ess();
+                        app.render(&mut terminal)?;
+                    }
+                    KeyCode::Char('l') => {
+                        app.toggle_change_log();
+                        app.render(&mut terminal)?;
+                    }
+                    KeyCode::Char('m') => {
+                        app.toggle_mark();
+                        app.render(&mut terminal)?;
+                    }
+                    KeyCode::Char('x') => {
+                        app.toggle_locate();
+                        app.render(&mut terminal)?;
+                    }
+                    KeyCode::Char('c') => {
+                        app.toggle_ie_diff();
+                        app.render(&mut terminal)?;
+                    }
                     KeyCode::Up => {
                         app.select_previous();
                         app.render(&mut terminal)?;
@@ -106,6 +289,10 @@ fn main() -> Result<(), io::Error> {
                         app.select_next();
                         app.render(&mut terminal)?;
                     }
+                    KeyCode::Enter if app.is_discovering() => {
+                        app.go_to_observed();
+                        app.render(&mut terminal)?;
+                    }
                     KeyCode::Enter => {
                         app.focus_next();
                         app.render(&mut terminal)?;
@@ -118,11 +305,13 @@ fn main() -> Result<(), io::Error> {
             Err(TryRecvError::Disconnected) => break,
         }
 
+        fast_scan.store(app.is_locating(), Ordering::Relaxed);
+        if app.locate_bell_due() {
+            terminal::bell()?;
+        }
+
         thread::sleep(Duration::from_millis(50));
     }
 
-    disable_raw_mode().unwrap();
-    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture).unwrap();
-
     Ok(())
 }
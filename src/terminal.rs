@@ -0,0 +1,35 @@
+use crossterm::{
+    event::DisableMouseCapture,
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::io::{self, Stdout, Write};
+use tui::{backend::CrosstermBackend, Terminal};
+
+pub fn enter() -> io::Result<Terminal<CrosstermBackend<Stdout>>> {
+    execute!(io::stdout(), EnterAlternateScreen).unwrap();
+    enable_raw_mode().unwrap();
+
+    let backend = CrosstermBackend::new(io::stdout());
+    let terminal = Terminal::new(backend).and_then(|mut terminal| {
+        terminal.clear()?;
+        Ok(terminal)
+    });
+    // Don't leave the terminal in raw mode on the alternate screen if it can't be drawn to
+    if terminal.is_err() {
+        leave();
+    }
+    terminal
+}
+
+// Rings the terminal bell
+pub fn bell() -> io::Result<()> {
+    let mut stdout = io::stdout();
+    stdout.write_all(b"\x07")?;
+    stdout.flush()
+}
+
+pub fn leave() {
+    disable_raw_mode().unwrap();
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture).unwrap();
+}
@@ -1,23 +1,43 @@
-use crate::widgets::TableSortOrder;
+use crate::{
+    analysis::{
+        hidden_ssid_len, is_dfs, is_hidden, is_legacy, security, Allowlist, ApMld, IeSearch,
+        MldLink, PathLossModel, ScanAnalysis,
+    },
+    decode::{display_ssid, min_basic_rate, truncate_to_width, BssLoad, Country, Feature},
+    widgets::TableSortOrder,
+};
 use kawaiifi::Bss;
-use std::{fmt::Display, iter::Iterator, ops::Deref};
+use std::{cmp::Ordering, collections::HashSet, fmt::Display, iter::Iterator, ops::Deref};
 use tui::{
     buffer::Buffer,
     layout::{Constraint, Rect},
     style::{Color, Modifier, Style},
     widgets::{Block, Borders, Row, StatefulWidget, Table, TableState},
 };
+use unicode_width::UnicodeWidthStr;
+
+// Escaped SSIDs can be several times longer than the 32 octets an SSID is limited to
+const MAX_SSID_WIDTH: usize = 32;
 
 pub struct BssTable;
 
 #[derive(Debug)]
 pub struct BssTableState {
+    all_scan_results: Vec<Bss>,
+    // The scan results that pass the search, in the order they're shown
     scan_results: Vec<Bss>,
     rows: Vec<BssTableRow>,
     column_headers: Vec<BssTableColumnHeader>,
     state: TableState,
     is_focused: bool,
     sorting: (BssTableColumnHeader, TableSortOrder),
+    analysis: ScanAnalysis,
+    // BSSIDs marked for comparison, oldest first
+    marked: Vec<String>,
+    search: Option<IeSearch>,
+    hidden_only: bool,
+    // Whether the links of an AP MLD are shown as a single row
+    group_mlds: bool,
 }
 
 #[derive(Debug)]
@@ -34,9 +54,21 @@ pub enum BssTableColumnHeader {
     Band,
     Frequency,
     Signal,
+    Distance,
     WiFiProtocols,
     Security,
     MaxRate,
+    MinBasicRate,
+    Interference,
+    Stations,
+    Utilization,
+    AdmissionCapacity,
+    IeChanges,
+    Findings,
+    Lint,
+    Ess,
+    Country,
+    Feature(Feature),
 }
 
 impl BssTable {
@@ -49,6 +81,22 @@ impl StatefulWidget for BssTable {
     type State = BssTableState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let mut title = match &state.search {
+            Some(search) => format!(
+                "Basic Service Sets (search \"{}\": {} of {})",
+                search.query(),
+                state.scan_results.len(),
+                state.all_scan_results.len()
+            ),
+            None => "Basic Service Sets".to_string(),
+        };
+        if state.hidden_only {
+            title.push_str(" - hidden only");
+        }
+        if state.analysis.rogue_count() > 0 {
+            title.push_str(&format!(" - {} rogue", state.analysis.rogue_count()));
+        }
+
         Table::new(
             state.header().iter().map(|column| {
                 if *column == state.sorting.0 {
@@ -57,11 +105,32 @@ impl StatefulWidget for BssTable {
                     format!("{}", column.to_string())
                 }
             }),
-            state.rows.iter().map(|row| Row::Data(row.iter())),
+            state
+                .rows
+                .iter()
+                .zip(&state.scan_results)
+                .map(|(row, bss)| {
+                    let style = if !state.analysis.rogue_reasons(bss).is_empty() {
+                        Style::default().fg(Color::Red)
+                    } else if state.marked.contains(&bss.bssid().to_string()) {
+                        Style::default().fg(Color::Cyan)
+                    } else if state.analysis.changes().changed_in_last_scan(bss) {
+                        Style::default().fg(Color::Magenta)
+                    } else {
+                        Style::default()
+                    };
+
+                    // Hidden SSIDs learned from earlier scans are shown in italics
+                    if state.analysis.learned_ssid(bss).is_some() {
+                        Row::StyledData(row.iter(), style.add_modifier(Modifier::ITALIC))
+                    } else {
+                        Row::StyledData(row.iter(), style)
+                    }
+                }),
         )
         .block(
             Block::default()
-                .title("Basic Service Sets")
+                .title(title.as_str())
                 .borders(Borders::ALL)
                 .border_style(if state.is_focused {
                     Style::default().fg(Color::Yellow)
@@ -69,15 +138,7 @@ impl StatefulWidget for BssTable {
                     Style::default()
                 }),
         )
-        .widths(
-            &state
-                .column_headers
-                .iter()
-                .map(|_| {
-                    Constraint::Percentage((100f64 / state.column_headers.len() as f64) as u16)
-                })
-                .collect::<Vec<Constraint>>(),
-        )
+        .widths(&state.column_widths())
         .style(Style::default())
         .column_spacing(1)
         .highlight_style(
@@ -106,8 +167,63 @@ impl BssTableState {
         self.is_focused = false;
     }
 
-    pub fn set_scan_results(&mut self, mut scan_results: Vec<Bss>) {
-        scan_results.sort_by_column(self.sorting.0, self.sorting.1);
+    pub fn set_scan_results(&mut self, scan_results: Vec<Bss>) {
+        self.analysis.update(&scan_results);
+        self.all_scan_results = scan_results;
+        self.apply_search();
+    }
+
+    pub fn set_allowlist(&mut self, allowlist: Allowlist) {
+        self.analysis.set_allowlist(allowlist);
+    }
+
+    pub fn set_path_loss_model(&mut self, path_loss_model: PathLossModel) {
+        self.analysis.set_path_loss_model(path_loss_model);
+        self.apply_search();
+    }
+
+    // Shows or hides the feature matrix columns together
+    pub fn toggle_feature_columns(&mut self) {
+        let is_feature =
+            |column: &BssTableColumnHeader| matches!(column, BssTableColumnHeader::Feature(_));
+
+        if self.column_headers.iter().any(is_feature) {
+            self.column_headers.retain(|column| !is_feature(column));
+        } else {
+            self.column_headers.extend(
+                Feature::ALL
+                    .iter()
+                    .map(|feature| BssTableColumnHeader::Feature(*feature)),
+            );
+        }
+        self.apply_search();
+    }
+
+    pub fn set_search(&mut self, search: Option<IeSearch>) {
+        self.search = search;
+        self.apply_search();
+    }
+
+    // Shows the scan results that pass the search, keeping the selected BSS selected
+    fn apply_search(&mut self) {
+        let mut scan_results = self
+            .all_scan_results
+            .iter()
+            .filter(|bss| self.search.iter().all(|search| search.matches(bss)))
+            .filter(|bss| !self.hidden_only || is_hidden(bss))
+            .cloned()
+            .collect::<Vec<Bss>>();
+        scan_results.sort_by_column(self.sorting.0, self.sorting.1, &self.analysis);
+
+        // Keep the highest sorted link of each AP MLD to represent it
+        if self.group_mlds {
+            let mut mld_macs = HashSet::new();
+            let analysis = &self.analysis;
+            scan_results.retain(|bss| match analysis.mld(bss) {
+                Some(mld) => mld_macs.insert(mld.mld_mac.clone()),
+                None => true,
+            });
+        }
         let previously_selected_bss = {
             if let Some(selected) = self.state.selected() {
                 self.scan_results.get(selected)
@@ -126,8 +242,10 @@ impl BssTableState {
             );
         }
 
-        // Make sure that a BSS is always selected
-        if self.state.selected().is_none() && !scan_results.is_empty() {
+        // Make sure that a BSS is always selected, unless the search hides every BSS
+        if scan_results.is_empty() {
+            self.state.select(None);
+        } else if self.state.selected().is_none() {
             self.state.select(Some(0));
         }
 
@@ -135,7 +253,7 @@ impl BssTableState {
         self.rows = self
             .scan_results
             .iter()
-            .map(|bss| BssTableRow::new(bss, &self.column_headers))
+            .map(|bss| BssTableRow::new(bss, &self.column_headers, &self.analysis, self.group_mlds))
             .collect();
     }
 
@@ -165,22 +283,101 @@ impl BssTableState {
         }
     }
 
+    // Marks the selected BSS for comparison, replacing the oldest mark if two are already marked
+    pub fn toggle_mark_selected(&mut self) {
+        if let Some(bssid) = self.selected_bss().map(|bss| bss.bssid().to_string()) {
+            if let Some(position) = self.marked.iter().position(|marked| *marked == bssid) {
+                self.marked.remove(position);
+            } else {
+                if self.marked.len() == 2 {
+                    self.marked.remove(0);
+                }
+                self.marked.push(bssid);
+            }
+        }
+    }
+
+    pub fn marked_bss(&self) -> Vec<&Bss> {
+        self.marked
+            .iter()
+            .filter_map(|bssid| self.bss(bssid))
+            .collect()
+    }
+
+    // Selects the row showing the BSS, which may be the row of its AP MLD. Returns whether the
+    // BSS is shown at all.
+    pub fn select_bssid(&mut self, bssid: &str) -> bool {
+        let analysis = &self.analysis;
+        let group_mlds = self.group_mlds;
+        let position = self.scan_results.iter().position(|bss| {
+            bss.bssid().to_string().to_lowercase() == bssid
+                || (group_mlds
+                    && analysis
+                        .mld(bss)
+                        .iter()
+                        .any(|mld| mld.links.iter().any(|link| link.bssid == bssid)))
+        });
+
+        if position.is_some() {
+            self.state.select(position);
+        }
+        position.is_some()
+    }
+
+    pub fn bss(&self, bssid: &str) -> Option<&Bss> {
+        self.all_scan_results
+            .iter()
+            .find(|bss| bss.bssid().to_string() == bssid)
+    }
+
+    pub fn analysis(&self) -> &ScanAnalysis {
+        &self.analysis
+    }
+
     pub fn header(&self) -> &Vec<BssTableColumnHeader> {
         &self.column_headers
     }
 
-    pub fn sort(&mut self, column: BssTableColumnHeader, sort_order: TableSortOrder) {
-        self.scan_results.sort_by_column(column, sort_order);
-        self.rows = self
-            .scan_results
+    pub fn rows(&self) -> impl Iterator<Item = &[String]> {
+        self.rows.iter().map(|row| row.as_slice())
+    }
+
+    // Each column is as wide as its widest value, or its header with room for the sort marker
+    fn column_widths(&self) -> Vec<Constraint> {
+        self.column_headers
             .iter()
-            .map(|bss| BssTableRow::new(bss, &self.column_headers))
-            .collect();
+            .enumerate()
+            .map(|(i, column)| {
+                let width = self
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.get(i))
+                    .map(|value| value.width())
+                    .max()
+                    .unwrap_or_default()
+                    .max(column.to_string().width() + 1);
+                Constraint::Length(width as u16)
+            })
+            .collect()
+    }
+
+    pub fn sort(&mut self, column: BssTableColumnHeader, sort_order: TableSortOrder) {
         self.sorting = (column, sort_order);
+        self.apply_search();
         if !self.scan_results.is_empty() {
             self.state.select(Some(0));
         }
     }
+
+    pub fn toggle_hidden_only(&mut self) {
+        self.hidden_only = !self.hidden_only;
+        self.apply_search();
+    }
+
+    pub fn toggle_mld_grouping(&mut self) {
+        self.group_mlds = !self.group_mlds;
+        self.apply_search();
+    }
 }
 
 impl Default for BssTableState {
@@ -194,43 +391,175 @@ impl Default for BssTableState {
                 BssTableColumnHeader::Band,
                 BssTableColumnHeader::Frequency,
                 BssTableColumnHeader::Signal,
+                BssTableColumnHeader::Distance,
                 BssTableColumnHeader::WiFiProtocols,
                 BssTableColumnHeader::Security,
                 BssTableColumnHeader::MaxRate,
+                BssTableColumnHeader::MinBasicRate,
+                BssTableColumnHeader::Interference,
+                BssTableColumnHeader::Stations,
+                BssTableColumnHeader::Utilization,
+                BssTableColumnHeader::AdmissionCapacity,
+                BssTableColumnHeader::IeChanges,
+                BssTableColumnHeader::Findings,
+                BssTableColumnHeader::Lint,
+                BssTableColumnHeader::Ess,
+                BssTableColumnHeader::Country,
             ],
+            all_scan_results: Vec::default(),
             scan_results: Vec::default(),
             rows: Vec::default(),
             state: TableState::default(),
             is_focused: false,
             sorting: (BssTableColumnHeader::Bssid, TableSortOrder::Descending),
+            analysis: ScanAnalysis::default(),
+            marked: Vec::new(),
+            search: None,
+            group_mlds: true,
+            hidden_only: false,
         }
     }
 }
 
 impl BssTableRow {
-    fn new(bss: &Bss, column_headers: &[BssTableColumnHeader]) -> Self {
+    fn new(
+        bss: &Bss,
+        column_headers: &[BssTableColumnHeader],
+        analysis: &ScanAnalysis,
+        group_mlds: bool,
+    ) -> Self {
+        let bss_load = BssLoad::from_ies(bss.ies()).unwrap_or_default();
+        let mld = analysis.mld(bss).filter(|_| group_mlds);
+
         BssTableRow {
             values: column_headers
                 .iter()
                 .map(|column_header| match column_header {
-                    BssTableColumnHeader::Bssid => bss.bssid().to_string(),
-                    BssTableColumnHeader::Ssid => bss.ssid().unwrap_or_default().to_string(),
-                    BssTableColumnHeader::Channel => bss.channel().number().to_string(),
+                    BssTableColumnHeader::Bssid => match mld {
+                        Some(mld) => format!("MLD {} ({} links)", mld.mld_mac, mld.links.len()),
+                        None => bss.bssid().to_string(),
+                    },
+                    BssTableColumnHeader::Ssid => {
+                        truncate_to_width(&ssid_label(bss, analysis), MAX_SSID_WIDTH)
+                    }
+                    BssTableColumnHeader::Channel => match mld {
+                        Some(mld) => mld_links(mld, |link| channel_label(link.channel, link.dfs)),
+                        None => channel_label(bss.channel().number(), is_dfs(bss)),
+                    },
                     BssTableColumnHeader::ChannelWidth => bss.channel().width().to_string(),
-                    BssTableColumnHeader::Band => bss.channel().band().to_string(),
+                    BssTableColumnHeader::Band => match mld {
+                        Some(mld) => mld_links(mld, |link| {
+                            link.band.map(|band| band.to_string()).unwrap_or_default()
+                        }),
+                        None => bss.channel().band().to_string(),
+                    },
                     BssTableColumnHeader::Frequency => {
                         format!("{} MHz", bss.channel().center_freq_mhz())
                     }
-                    BssTableColumnHeader::Signal => format!("{} dBm", bss.signal_dbm()),
+                    BssTableColumnHeader::Signal => match mld {
+                        Some(mld) => format!(
+                            "{} dBm",
+                            mld_links(mld, |link| link
+                                .signal_dbm
+                                .map(|signal| signal.to_string())
+                                .unwrap_or_else(|| "-".to_string()))
+                        ),
+                        None => format!("{} dBm", bss.signal_dbm()),
+                    },
+                    BssTableColumnHeader::Distance => analysis.distance(bss).to_string(),
                     BssTableColumnHeader::WiFiProtocols => bss.wifi_protocols().to_string(),
-                    BssTableColumnHeader::Security => "None".to_string(),
+                    BssTableColumnHeader::Security => security(bss),
                     BssTableColumnHeader::MaxRate => "0.0 Mbps".to_string(),
+                    BssTableColumnHeader::MinBasicRate => {
+                        match (min_basic_rate(bss.ies()), is_legacy(bss)) {
+                            (Some(rate), true) => format!("{} Mbps !", rate.mbps()),
+                            (Some(rate), false) => format!("{} Mbps", rate.mbps()),
+                            (None, true) => "!".to_string(),
+                            (None, false) => String::new(),
+                        }
+                    }
+                    BssTableColumnHeader::Interference => analysis
+                        .interference(bss)
+                        .map(|interference| interference.to_string())
+                        .unwrap_or_default(),
+                    BssTableColumnHeader::Stations => bss_load
+                        .station_count
+                        .map(|count| count.to_string())
+                        .unwrap_or_default(),
+                    BssTableColumnHeader::Utilization => bss_load
+                        .channel_utilization_percent()
+                        .map(|percent| format!("{:.0}%", percent))
+                        .unwrap_or_default(),
+                    BssTableColumnHeader::AdmissionCapacity => bss_load
+                        .available_admission_capacity_us()
+                        .map(|capacity| format!("{} us/s", capacity))
+                        .unwrap_or_default(),
+                    BssTableColumnHeader::IeChanges => match analysis.changes().change_count(bss) {
+                        0 => String::new(),
+                        count if analysis.changes().changed_in_last_scan(bss) => {
+                            format!("{} !", count)
+                        }
+                        count => count.to_string(),
+                    },
+                    BssTableColumnHeader::Findings => match analysis.findings(bss) {
+                        [] => String::new(),
+                        findings => format!("{} ({})", findings.len(), findings[0].severity),
+                    },
+                    BssTableColumnHeader::Lint => match analysis.lint_problems(bss).len() {
+                        0 => String::new(),
+                        count => count.to_string(),
+                    },
+                    BssTableColumnHeader::Ess => match analysis.ess_deviation_count(bss) {
+                        0 => String::new(),
+                        count => count.to_string(),
+                    },
+                    BssTableColumnHeader::Country => match Country::from_ies(bss.ies()) {
+                        Some(country) if analysis.is_country_mismatch(bss) => {
+                            format!("{} !", country.code)
+                        }
+                        Some(country) => country.code,
+                        None => String::new(),
+                    },
+                    BssTableColumnHeader::Feature(feature) => {
+                        if feature.is_enabled(bss.ies()) {
+                            "Yes".to_string()
+                        } else {
+                            "No".to_string()
+                        }
+                    }
                 })
                 .collect(),
         }
     }
 }
 
+// The escaped SSID, or what is known about a hidden one
+fn ssid_label(bss: &Bss, analysis: &ScanAnalysis) -> String {
+    match (hidden_ssid_len(bss), analysis.learned_ssid(bss)) {
+        (Some(_), Some(learned)) => learned.to_string(),
+        (Some(len), None) => format!("<hidden len={}>", len),
+        (None, _) => display_ssid(bss.ies()).unwrap_or_default(),
+    }
+}
+
+// Marks the BSSs that need radar detection on any of their channels
+fn channel_label(channel: u8, dfs: bool) -> String {
+    if dfs {
+        format!("{} DFS", channel)
+    } else {
+        channel.to_string()
+    }
+}
+
+// One value per link, e.g. "1/36/37" for the channels of a tri-band AP MLD
+fn mld_links(mld: &ApMld, value: impl Fn(&MldLink) -> String) -> String {
+    mld.links
+        .iter()
+        .map(value)
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
 impl Deref for BssTableRow {
     type Target = Vec<String>;
 
@@ -240,14 +569,25 @@ impl Deref for BssTableRow {
 }
 
 trait Sortable {
-    fn sort_by_column(&mut self, column: BssTableColumnHeader, sort_order: TableSortOrder);
+    fn sort_by_column(
+        &mut self,
+        column: BssTableColumnHeader,
+        sort_order: TableSortOrder,
+        analysis: &ScanAnalysis,
+    );
 }
 
 impl Sortable for Vec<Bss> {
-    fn sort_by_column(&mut self, column: BssTableColumnHeader, sort_order: TableSortOrder) {
+    fn sort_by_column(
+        &mut self,
+        column: BssTableColumnHeader,
+        sort_order: TableSortOrder,
+        analysis: &ScanAnalysis,
+    ) {
+        let load = |bss: &Bss| BssLoad::from_ies(bss.ies()).unwrap_or_default();
         let compare_bss = |a: &Bss, b: &Bss| match column {
             BssTableColumnHeader::Bssid => a.bssid().cmp(b.bssid()),
-            BssTableColumnHeader::Ssid => a.ssid().cmp(&b.ssid()),
+            BssTableColumnHeader::Ssid => ssid_label(a, analysis).cmp(&ssid_label(b, analysis)),
             BssTableColumnHeader::Channel => a.channel().number().cmp(&b.channel().number()),
             BssTableColumnHeader::ChannelWidth => a.channel().width().cmp(&b.channel().width()),
             BssTableColumnHeader::Band => a.channel().band().cmp(&b.channel().band()),
@@ -256,7 +596,56 @@ impl Sortable for Vec<Bss> {
                 .center_freq_mhz()
                 .cmp(&b.channel().center_freq_mhz()),
             BssTableColumnHeader::Signal => a.signal_dbm().cmp(&b.signal_dbm()),
+            BssTableColumnHeader::Distance => analysis
+                .distance(a)
+                .meters
+                .partial_cmp(&analysis.distance(b).meters)
+                .unwrap_or(Ordering::Equal),
             BssTableColumnHeader::WiFiProtocols => a.wifi_protocols().cmp(&b.wifi_protocols()),
+            BssTableColumnHeader::Security => security(a).cmp(&security(b)),
+            BssTableColumnHeader::MinBasicRate => {
+                let rate = |bss: &Bss| min_basic_rate(bss.ies()).map(|rate| rate.value);
+                rate(a).cmp(&rate(b))
+            }
+            BssTableColumnHeader::Interference => analysis
+                .interference(a)
+                .partial_cmp(&analysis.interference(b))
+                .unwrap_or(Ordering::Equal),
+            BssTableColumnHeader::Stations => load(a).station_count.cmp(&load(b).station_count),
+            BssTableColumnHeader::Utilization => load(a)
+                .channel_utilization
+                .cmp(&load(b).channel_utilization),
+            BssTableColumnHeader::AdmissionCapacity => load(a)
+                .available_admission_capacity
+                .cmp(&load(b).available_admission_capacity),
+            BssTableColumnHeader::IeChanges => analysis
+                .changes()
+                .change_count(a)
+                .cmp(&analysis.changes().change_count(b)),
+            BssTableColumnHeader::Findings => {
+                let findings = |bss: &Bss| {
+                    let findings = analysis.findings(bss);
+                    (
+                        findings.first().map(|finding| finding.severity),
+                        findings.len(),
+                    )
+                };
+                findings(a).cmp(&findings(b))
+            }
+            BssTableColumnHeader::Lint => analysis
+                .lint_problems(a)
+                .len()
+                .cmp(&analysis.lint_problems(b).len()),
+            BssTableColumnHeader::Ess => analysis
+                .ess_deviation_count(a)
+                .cmp(&analysis.ess_deviation_count(b)),
+            BssTableColumnHeader::Country => {
+                let code = |bss: &Bss| Country::from_ies(bss.ies()).map(|country| country.code);
+                code(a).cmp(&code(b))
+            }
+            BssTableColumnHeader::Feature(feature) => feature
+                .is_enabled(a.ies())
+                .cmp(&feature.is_enabled(b.ies())),
             _ => a.bssid().cmp(b.bssid()),
         };
 
@@ -277,9 +666,21 @@ impl Display for BssTableColumnHeader {
             BssTableColumnHeader::Band => write!(f, "Band"),
             BssTableColumnHeader::Frequency => write!(f, "Frequency"),
             BssTableColumnHeader::Signal => write!(f, "Signal"),
+            BssTableColumnHeader::Distance => write!(f, "Distance"),
             BssTableColumnHeader::WiFiProtocols => write!(f, "Protocols"),
             BssTableColumnHeader::Security => write!(f, "Security"),
             BssTableColumnHeader::MaxRate => write!(f, "Max Rate"),
+            BssTableColumnHeader::MinBasicRate => write!(f, "Min Basic Rate"),
+            BssTableColumnHeader::Interference => write!(f, "Interference"),
+            BssTableColumnHeader::Stations => write!(f, "Stations"),
+            BssTableColumnHeader::Utilization => write!(f, "Utilization"),
+            BssTableColumnHeader::AdmissionCapacity => write!(f, "Adm. Capacity"),
+            BssTableColumnHeader::IeChanges => write!(f, "IE Changes"),
+            BssTableColumnHeader::Findings => write!(f, "Findings"),
+            BssTableColumnHeader::Lint => write!(f, "Lint"),
+            BssTableColumnHeader::Ess => write!(f, "ESS"),
+            BssTableColumnHeader::Country => write!(f, "Country"),
+            BssTableColumnHeader::Feature(feature) => write!(f, "{}", feature),
         }
     }
 }
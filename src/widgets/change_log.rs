@@ -0,0 +1,61 @@
+use crate::analysis::IeChange;
+use std::time::Instant;
+use tui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Row, Table, Widget},
+};
+
+pub struct ChangeLogPopup<'a> {
+    changes: &'a [IeChange],
+}
+
+impl<'a> ChangeLogPopup<'a> {
+    pub fn new(changes: &'a [IeChange]) -> ChangeLogPopup<'a> {
+        ChangeLogPopup { changes }
+    }
+}
+
+impl<'a> Widget for ChangeLogPopup<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let now = Instant::now();
+        // Newest first, and only as many as fit
+        let rows = self
+            .changes
+            .iter()
+            .rev()
+            .take(area.height as usize)
+            .map(|change| {
+                vec![
+                    format!("{}s ago", now.duration_since(change.time).as_secs()),
+                    change.bssid.clone(),
+                    change.ssid.clone(),
+                    change.element.clone(),
+                    change.description.clone(),
+                ]
+            })
+            .collect::<Vec<Vec<String>>>();
+
+        Clear.render(area, buf);
+        Table::new(
+            ["When", "BSSID", "SSID", "Element", "Change"].iter(),
+            rows.iter().map(|row| Row::Data(row.iter())),
+        )
+        .block(
+            Block::default()
+                .title("IE Change Log")
+                .borders(Borders::ALL),
+        )
+        .header_style(Style::default().add_modifier(Modifier::BOLD))
+        .widths(&[
+            Constraint::Percentage(8),
+            Constraint::Percentage(15),
+            Constraint::Percentage(15),
+            Constraint::Percentage(17),
+            Constraint::Percentage(45),
+        ])
+        .column_spacing(1)
+        .render(area, buf);
+    }
+}
@@ -0,0 +1,131 @@
+use crate::analysis::{ChannelAirtime, LegacyBandSummary};
+use tui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Row, Table, Widget},
+};
+
+// Beacons taking more than this much airtime leave noticeably less for data
+const HIGH_AIRTIME_PERCENT: f64 = 10.0;
+const ELEVATED_AIRTIME_PERCENT: f64 = 5.0;
+
+pub struct ChannelSummaryPopup<'a> {
+    channels: &'a [ChannelAirtime],
+    bands: &'a [LegacyBandSummary],
+}
+
+impl<'a> ChannelSummaryPopup<'a> {
+    pub fn new(
+        channels: &'a [ChannelAirtime],
+        bands: &'a [LegacyBandSummary],
+    ) -> ChannelSummaryPopup<'a> {
+        ChannelSummaryPopup { channels, bands }
+    }
+}
+
+impl<'a> Widget for ChannelSummaryPopup<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // One row per band plus the header and borders
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Length(self.bands.len() as u16 + 3),
+                    Constraint::Min(0),
+                ]
+                .as_ref(),
+            )
+            .split(area);
+
+        let band_rows = self
+            .bands
+            .iter()
+            .map(|band| {
+                vec![
+                    band.band.to_string(),
+                    band.bss_count.to_string(),
+                    band.legacy_count.to_string(),
+                ]
+            })
+            .collect::<Vec<Vec<String>>>();
+
+        let rows = self
+            .channels
+            .iter()
+            .map(|channel| {
+                (
+                    channel.airtime_percent,
+                    vec![
+                        channel.band.to_string(),
+                        if channel.dfs {
+                            format!("{} DFS", channel.channel)
+                        } else {
+                            channel.channel.to_string()
+                        },
+                        channel.bss_count.to_string(),
+                        format!("{:.1}", channel.beacons_per_second),
+                        format!("{} Mbps", channel.lowest_rate_mbps),
+                        format!("{:.1}%", channel.airtime_percent),
+                    ],
+                )
+            })
+            .collect::<Vec<(f64, Vec<String>)>>();
+
+        Clear.render(area, buf);
+        Table::new(
+            ["Band", "BSSs", "Legacy Rate BSSs"].iter(),
+            band_rows.iter().map(|row| Row::Data(row.iter())),
+        )
+        .block(
+            Block::default()
+                .title("BSSs with 802.11b basic rates or without HT per band")
+                .borders(Borders::ALL),
+        )
+        .header_style(Style::default().add_modifier(Modifier::BOLD))
+        .widths(&[
+            Constraint::Percentage(15),
+            Constraint::Percentage(15),
+            Constraint::Percentage(30),
+        ])
+        .column_spacing(1)
+        .render(chunks[0], buf);
+
+        Table::new(
+            [
+                "Band",
+                "Channel",
+                "BSSs",
+                "Beacons/s",
+                "Lowest Rate",
+                "Beacon Airtime",
+            ]
+            .iter(),
+            rows.iter().map(|(airtime_percent, row)| {
+                if *airtime_percent >= HIGH_AIRTIME_PERCENT {
+                    Row::StyledData(row.iter(), Style::default().fg(Color::Red))
+                } else if *airtime_percent >= ELEVATED_AIRTIME_PERCENT {
+                    Row::StyledData(row.iter(), Style::default().fg(Color::Yellow))
+                } else {
+                    Row::Data(row.iter())
+                }
+            }),
+        )
+        .block(
+            Block::default()
+                .title("Estimated beacon airtime per channel")
+                .borders(Borders::ALL),
+        )
+        .header_style(Style::default().add_modifier(Modifier::BOLD))
+        .widths(&[
+            Constraint::Percentage(15),
+            Constraint::Percentage(15),
+            Constraint::Percentage(10),
+            Constraint::Percentage(15),
+            Constraint::Percentage(20),
+            Constraint::Percentage(20),
+        ])
+        .column_spacing(1)
+        .render(chunks[1], buf);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::analysis::AnnouncedBss;
+use tui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Row, StatefulWidget, Table, TableState, Widget},
+};
+
+pub struct DiscoveryPopup<'a> {
+    announced: &'a [AnnouncedBss],
+}
+
+impl<'a> DiscoveryPopup<'a> {
+    pub fn new(announced: &'a [AnnouncedBss]) -> DiscoveryPopup<'a> {
+        DiscoveryPopup { announced }
+    }
+}
+
+impl<'a> StatefulWidget for DiscoveryPopup<'a> {
+    type State = TableState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let rows = self
+            .announced
+            .iter()
+            .map(|bss| {
+                (
+                    bss.observed_signal_dbm.is_some(),
+                    vec![
+                        bss.channel.to_string(),
+                        bss.bssid.clone(),
+                        bss.short_ssid
+                            .map(|short_ssid| format!("{:08x}", short_ssid))
+                            .unwrap_or_default(),
+                        bss.ssid.clone().unwrap_or_default(),
+                        format!(
+                            "{}{}",
+                            bss.announced_by.join(", "),
+                            if bss.colocated { " (co-located)" } else { "" }
+                        ),
+                        match bss.observed_signal_dbm {
+                            Some(signal) => format!("Observed, {} dBm", signal),
+                            None => "Announced".to_string(),
+                        },
+                    ],
+                )
+            })
+            .collect::<Vec<(bool, Vec<String>)>>();
+
+        let observed = rows.iter().filter(|(observed, _)| *observed).count();
+        let title = format!(
+            "6 GHz BSSs announced by 2.4/5 GHz BSSs ({} of {} observed, Enter: go to observed)",
+            observed,
+            rows.len()
+        );
+
+        Clear.render(area, buf);
+        let table = Table::new(
+            [
+                "Channel",
+                "BSSID",
+                "Short SSID",
+                "SSID",
+                "Announced By",
+                "Status",
+            ]
+            .iter(),
+            rows.iter().map(|(observed, row)| {
+                if *observed {
+                    Row::StyledData(row.iter(), Style::default().fg(Color::Green))
+                } else {
+                    Row::Data(row.iter())
+                }
+            }),
+        )
+        .block(Block::default().title(title.as_str()).borders(Borders::ALL))
+        .header_style(Style::default().add_modifier(Modifier::BOLD))
+        .widths(&[
+            Constraint::Percentage(8),
+            Constraint::Percentage(17),
+            Constraint::Percentage(10),
+            Constraint::Percentage(20),
+            Constraint::Percentage(30),
+            Constraint::Percentage(15),
+        ])
+        .column_spacing(1)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        StatefulWidget::render(table, area, buf, state);
+    }
+}
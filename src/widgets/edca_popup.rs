@@ -0,0 +1,157 @@
+use crate::{
+    analysis::{self, EdcaNote},
+    decode::{MuEdcaParameters, WmmParameters},
+};
+use kawaiifi::Bss;
+use tui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Row, Table, Widget},
+};
+
+pub struct EdcaPopup<'a> {
+    bss: &'a Bss,
+}
+
+impl<'a> EdcaPopup<'a> {
+    pub fn new(bss: &'a Bss) -> EdcaPopup<'a> {
+        EdcaPopup { bss }
+    }
+}
+
+impl<'a> Widget for EdcaPopup<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)].as_ref())
+            .split(area);
+
+        let wmm = WmmParameters::from_ies(self.bss.ies());
+        let wmm_rows = wmm
+            .iter()
+            .flat_map(|wmm| {
+                wmm.ac_parameters.iter().map(move |ac| {
+                    let notes = analysis::check_ac(ac, &wmm.ac_parameters);
+                    (
+                        row_style(&notes),
+                        vec![
+                            ac.access_category.to_string(),
+                            ac.aifsn.to_string(),
+                            ac.cw_min.to_string(),
+                            ac.cw_max.to_string(),
+                            format!("{} us", ac.txop_limit_us),
+                            ac.acm.to_string(),
+                            notes
+                                .iter()
+                                .map(|note| note.text.as_str())
+                                .collect::<Vec<&str>>()
+                                .join("; "),
+                        ],
+                    )
+                })
+            })
+            .collect::<Vec<(Style, Vec<String>)>>();
+
+        let wmm_title = match &wmm {
+            Some(wmm) => format!(
+                "WMM Parameters of {} (set {}, U-APSD {})",
+                self.bss.bssid(),
+                wmm.parameter_set_count(),
+                wmm.uapsd()
+            ),
+            None => format!("WMM Parameters of {}: not advertised", self.bss.bssid()),
+        };
+
+        let mu_edca = MuEdcaParameters::from_ies(self.bss.ies());
+        let mu_edca_rows = mu_edca
+            .iter()
+            .flat_map(|mu_edca| {
+                mu_edca.ac_parameters.iter().map(move |ac| {
+                    let notes = analysis::check_mu_ac(ac, &mu_edca.ac_parameters);
+                    (
+                        row_style(&notes),
+                        vec![
+                            ac.access_category.to_string(),
+                            ac.aifsn.to_string(),
+                            ac.cw_min.to_string(),
+                            ac.cw_max.to_string(),
+                            format!("{:.1} ms", ac.timer_us as f64 / 1000.0),
+                            ac.acm.to_string(),
+                            notes
+                                .iter()
+                                .map(|note| note.text.as_str())
+                                .collect::<Vec<&str>>()
+                                .join("; "),
+                        ],
+                    )
+                })
+            })
+            .collect::<Vec<(Style, Vec<String>)>>();
+
+        let mu_edca_title = match &mu_edca {
+            Some(mu_edca) => format!("HE MU EDCA Parameters (set {})", mu_edca.qos_info & 0x0f),
+            None => "HE MU EDCA Parameters: not advertised".to_string(),
+        };
+
+        Clear.render(area, buf);
+        Table::new(
+            ["AC", "AIFSN", "CWmin", "CWmax", "TXOP", "ACM", "Notes"].iter(),
+            wmm_rows
+                .iter()
+                .map(|(style, row)| Row::StyledData(row.iter(), *style)),
+        )
+        .block(
+            Block::default()
+                .title(wmm_title.as_str())
+                .borders(Borders::ALL),
+        )
+        .header_style(Style::default().add_modifier(Modifier::BOLD))
+        .widths(&[
+            Constraint::Percentage(8),
+            Constraint::Percentage(7),
+            Constraint::Percentage(7),
+            Constraint::Percentage(7),
+            Constraint::Percentage(9),
+            Constraint::Percentage(7),
+            Constraint::Percentage(55),
+        ])
+        .column_spacing(1)
+        .render(chunks[0], buf);
+
+        Table::new(
+            ["AC", "AIFSN", "CWmin", "CWmax", "MU Timer", "ACM", "Notes"].iter(),
+            mu_edca_rows
+                .iter()
+                .map(|(style, row)| Row::StyledData(row.iter(), *style)),
+        )
+        .block(
+            Block::default()
+                .title(mu_edca_title.as_str())
+                .borders(Borders::ALL),
+        )
+        .header_style(Style::default().add_modifier(Modifier::BOLD))
+        .widths(&[
+            Constraint::Percentage(8),
+            Constraint::Percentage(7),
+            Constraint::Percentage(7),
+            Constraint::Percentage(7),
+            Constraint::Percentage(9),
+            Constraint::Percentage(7),
+            Constraint::Percentage(55),
+        ])
+        .column_spacing(1)
+        .render(chunks[1], buf);
+    }
+}
+
+// Red for suspicious values, yellow for values that only differ from the defaults
+fn row_style(notes: &[EdcaNote]) -> Style {
+    if notes.iter().any(|note| note.suspicious) {
+        Style::default().fg(Color::Red)
+    } else if !notes.is_empty() {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    }
+}
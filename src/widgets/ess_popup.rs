@@ -0,0 +1,67 @@
+use crate::analysis::EssDeviation;
+use tui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Row, Table, Widget},
+};
+
+pub struct EssPopup<'a> {
+    deviations: &'a [EssDeviation],
+}
+
+impl<'a> EssPopup<'a> {
+    pub fn new(deviations: &'a [EssDeviation]) -> EssPopup<'a> {
+        EssPopup { deviations }
+    }
+}
+
+impl<'a> Widget for EssPopup<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let rows = self
+            .deviations
+            .iter()
+            .map(|deviation| {
+                vec![
+                    deviation.ssid.clone(),
+                    deviation
+                        .bssid
+                        .clone()
+                        .unwrap_or_else(|| "Inconsistent".to_string()),
+                    deviation.attribute.clone(),
+                    deviation.value.clone(),
+                    match &deviation.majority {
+                        Some(majority) => format!(
+                            "{} ({} of {})",
+                            majority, deviation.majority_count, deviation.total
+                        ),
+                        None => format!("None of {} APs", deviation.total),
+                    },
+                ]
+            })
+            .collect::<Vec<Vec<String>>>();
+
+        let title = if self.deviations.is_empty() {
+            "ESS consistency: every AP matches its ESS".to_string()
+        } else {
+            format!("ESS consistency: {} deviations", self.deviations.len())
+        };
+
+        Clear.render(area, buf);
+        Table::new(
+            ["SSID", "BSSID", "Attribute", "Value", "Majority"].iter(),
+            rows.iter().map(|row| Row::Data(row.iter())),
+        )
+        .block(Block::default().title(title.as_str()).borders(Borders::ALL))
+        .header_style(Style::default().add_modifier(Modifier::BOLD))
+        .widths(&[
+            Constraint::Percentage(15),
+            Constraint::Percentage(15),
+            Constraint::Percentage(15),
+            Constraint::Percentage(25),
+            Constraint::Percentage(30),
+        ])
+        .column_spacing(1)
+        .render(area, buf);
+    }
+}
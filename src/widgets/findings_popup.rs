@@ -0,0 +1,64 @@
+use crate::analysis::{Finding, Severity};
+use tui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Row, Table, Widget},
+};
+
+pub struct FindingsPopup<'a> {
+    bssid: &'a str,
+    findings: &'a [Finding],
+}
+
+impl<'a> FindingsPopup<'a> {
+    pub fn new(bssid: &'a str, findings: &'a [Finding]) -> FindingsPopup<'a> {
+        FindingsPopup { bssid, findings }
+    }
+}
+
+impl<'a> Widget for FindingsPopup<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let rows = self
+            .findings
+            .iter()
+            .map(|finding| {
+                (
+                    finding.severity,
+                    vec![
+                        finding.severity.to_string(),
+                        finding.title.clone(),
+                        finding.detail.clone(),
+                    ],
+                )
+            })
+            .collect::<Vec<(Severity, Vec<String>)>>();
+
+        let title = if self.findings.is_empty() {
+            format!("Security audit of {}: no findings", self.bssid)
+        } else {
+            format!("Security audit of {}", self.bssid)
+        };
+
+        Clear.render(area, buf);
+        Table::new(
+            ["Severity", "Finding", "Detail"].iter(),
+            rows.iter().map(|(severity, row)| match severity {
+                Severity::Critical | Severity::High => {
+                    Row::StyledData(row.iter(), Style::default().fg(Color::Red))
+                }
+                Severity::Medium => Row::StyledData(row.iter(), Style::default().fg(Color::Yellow)),
+                _ => Row::Data(row.iter()),
+            }),
+        )
+        .block(Block::default().title(title.as_str()).borders(Borders::ALL))
+        .header_style(Style::default().add_modifier(Modifier::BOLD))
+        .widths(&[
+            Constraint::Percentage(10),
+            Constraint::Percentage(30),
+            Constraint::Percentage(60),
+        ])
+        .column_spacing(1)
+        .render(area, buf);
+    }
+}
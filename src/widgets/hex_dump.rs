@@ -0,0 +1,156 @@
+use std::ops::Range;
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Paragraph, Widget},
+};
+
+// Lines hold the most of these byte counts that fit, so offsets stay round numbers
+const BYTES_PER_LINE: &[usize] = &[16, 8, 4, 2, 1];
+
+pub struct HexDump<'a> {
+    bytes: &'a [u8],
+    highlight: Option<Range<usize>>,
+    block: Option<Block<'a>>,
+}
+
+impl<'a> HexDump<'a> {
+    pub fn new(bytes: &'a [u8]) -> HexDump<'a> {
+        HexDump {
+            bytes,
+            highlight: None,
+            block: None,
+        }
+    }
+
+    pub fn highlight(mut self, highlight: Option<Range<usize>>) -> HexDump<'a> {
+        self.highlight = highlight;
+        self
+    }
+
+    pub fn block(mut self, block: Block<'a>) -> HexDump<'a> {
+        self.block = Some(block);
+        self
+    }
+
+    fn style(&self, offset: usize) -> Style {
+        match &self.highlight {
+            Some(highlight) if highlight.contains(&offset) => Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            _ => Style::default(),
+        }
+    }
+
+    // A line is a 4 digit offset and 2 spaces, 3 columns per byte in hex, a space and 1 column
+    // per byte as ASCII
+    fn bytes_per_line(width: u16) -> usize {
+        BYTES_PER_LINE
+            .iter()
+            .copied()
+            .find(|bytes| 7 + 4 * bytes <= width as usize)
+            .unwrap_or(1)
+    }
+
+    // The first line to show, so that as much of the highlight as fits is in view
+    fn scroll(&self, bytes_per_line: usize, height: u16) -> u16 {
+        let height = height.max(1) as usize;
+        let (first_line, last_line) = match &self.highlight {
+            Some(highlight) if !highlight.is_empty() => (
+                highlight.start / bytes_per_line,
+                (highlight.end - 1) / bytes_per_line,
+            ),
+            _ => return 0,
+        };
+        if last_line < height {
+            0
+        } else if last_line - first_line < height {
+            (last_line + 1 - height) as u16
+        } else {
+            first_line as u16
+        }
+    }
+}
+
+impl<'a> Widget for HexDump<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let inner = match &self.block {
+            Some(block) => block.inner(area),
+            None => area,
+        };
+        let bytes_per_line = HexDump::bytes_per_line(inner.width);
+
+        // Each line is an offset, the bytes in hex and then the bytes as ASCII
+        let lines = self
+            .bytes
+            .chunks(bytes_per_line)
+            .enumerate()
+            .map(|(line, chunk)| {
+                let line_offset = line * bytes_per_line;
+                let mut spans = vec![Span::raw(format!("{:04x}  ", line_offset))];
+
+                for (i, byte) in chunk.iter().enumerate() {
+                    spans.push(Span::styled(
+                        format!("{:02x}", byte),
+                        self.style(line_offset + i),
+                    ));
+                    spans.push(Span::raw(" "));
+                }
+                spans.push(Span::raw("   ".repeat(bytes_per_line - chunk.len()) + " "));
+
+                for (i, byte) in chunk.iter().enumerate() {
+                    let character = if byte.is_ascii_graphic() || *byte == b' ' {
+                        *byte as char
+                    } else {
+                        '.'
+                    };
+                    spans.push(Span::styled(
+                        character.to_string(),
+                        self.style(line_offset + i),
+                    ));
+                }
+
+                Spans::from(spans)
+            })
+            .collect::<Vec<Spans>>();
+
+        let mut paragraph =
+            Paragraph::new(lines).scroll((self.scroll(bytes_per_line, inner.height), 0));
+        if let Some(block) = self.block {
+            paragraph = paragraph.block(block);
+        }
+        paragraph.render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fits_bytes_per_line_to_width() {
+        assert_eq!(HexDump::bytes_per_line(71), 16);
+        assert_eq!(HexDump::bytes_per_line(70), 8);
+        assert_eq!(HexDump::bytes_per_line(39), 8);
+        assert_eq!(HexDump::bytes_per_line(20), 2);
+        assert_eq!(HexDump::bytes_per_line(0), 1);
+    }
+
+    #[test]
+    fn scrolls_to_highlight() {
+        let bytes = [0; 256];
+        let scroll = |highlight, height| {
+            HexDump::new(&bytes)
+                .highlight(Some(highlight))
+                .scroll(16, height)
+        };
+        assert_eq!(scroll(0..4, 4), 0);
+        assert_eq!(scroll(60..64, 4), 0);
+        assert_eq!(scroll(200..210, 4), 10);
+        assert_eq!(scroll(200..240, 4), 11);
+        assert_eq!(scroll(32..160, 4), 2);
+    }
+}
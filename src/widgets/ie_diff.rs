@@ -0,0 +1,154 @@
+use crate::analysis::{self, DiffStatus};
+use kawaiifi::Bss;
+use tui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Row, StatefulWidget, Table, TableState},
+};
+
+pub struct IeDiffView;
+
+#[derive(Debug)]
+pub struct IeDiffState {
+    left_bssid: String,
+    right_bssid: String,
+    rows: Vec<IeDiffRow>,
+    state: TableState,
+}
+
+#[derive(Debug)]
+struct IeDiffRow {
+    values: Vec<String>,
+    style: Style,
+}
+
+impl IeDiffView {
+    pub fn new() -> IeDiffView {
+        IeDiffView {}
+    }
+}
+
+impl StatefulWidget for IeDiffView {
+    type State = IeDiffState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let title = format!(
+            "IE Comparison: {} vs {}",
+            state.left_bssid, state.right_bssid
+        );
+
+        StatefulWidget::render(
+            Table::new(
+                [
+                    "Element",
+                    state.left_bssid.as_str(),
+                    state.right_bssid.as_str(),
+                ]
+                .iter(),
+                state
+                    .rows
+                    .iter()
+                    .map(|row| Row::StyledData(row.values.iter(), row.style)),
+            )
+            .block(
+                Block::default()
+                    .title(title.as_str())
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Yellow)),
+            )
+            .widths(&[
+                Constraint::Percentage(30),
+                Constraint::Percentage(35),
+                Constraint::Percentage(35),
+            ])
+            .column_spacing(1)
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
+            area,
+            buf,
+            &mut state.state,
+        );
+    }
+}
+
+impl IeDiffState {
+    pub fn new(left: &Bss, right: &Bss) -> Self {
+        let mut state = IeDiffState {
+            left_bssid: String::new(),
+            right_bssid: String::new(),
+            rows: Vec::new(),
+            state: TableState::default(),
+        };
+        state.set_bss(left, right);
+        state.state.select(Some(0));
+        state
+    }
+
+    pub fn bssids(&self) -> (&str, &str) {
+        (&self.left_bssid, &self.right_bssid)
+    }
+
+    pub fn set_bss(&mut self, left: &Bss, right: &Bss) {
+        self.left_bssid = left.bssid().to_string();
+        self.right_bssid = right.bssid().to_string();
+        self.rows = Vec::new();
+
+        for ie_diff in analysis::diff_ies(left, right) {
+            let (left_present, right_present, style) = match ie_diff.status {
+                DiffStatus::Same => ("present", "present", Style::default()),
+                DiffStatus::Different => ("present", "present", Style::default().fg(Color::Yellow)),
+                DiffStatus::LeftOnly => ("present", "missing", Style::default().fg(Color::Red)),
+                DiffStatus::RightOnly => ("missing", "present", Style::default().fg(Color::Red)),
+            };
+            self.rows.push(IeDiffRow {
+                values: vec![
+                    ie_diff.name,
+                    left_present.to_string(),
+                    right_present.to_string(),
+                ],
+                style: style.add_modifier(Modifier::BOLD),
+            });
+
+            for field in ie_diff.fields {
+                self.rows.push(IeDiffRow {
+                    style: if field.is_different() {
+                        Style::default().fg(Color::Yellow)
+                    } else {
+                        Style::default()
+                    },
+                    values: vec![
+                        String::new(),
+                        indent(field.depth, field.left.unwrap_or_default()),
+                        indent(field.depth, field.right.unwrap_or_default()),
+                    ],
+                });
+            }
+        }
+
+        if let Some(selected) = self.state.selected() {
+            if selected >= self.rows.len() {
+                self.state.select(self.rows.len().checked_sub(1));
+            }
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if let Some(selected) = self.state.selected() {
+            if self.rows.len() > selected + 1 {
+                self.state.select(Some(selected + 1));
+            }
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if let Some(selected) = self.state.selected() {
+            if selected > 0 {
+                self.state.select(Some(selected - 1));
+            }
+        }
+    }
+}
+
+fn indent(depth: usize, text: String) -> String {
+    format!("{}{}", "  ".repeat(depth), text)
+}
@@ -1,26 +1,42 @@
+use crate::{
+    decode::{self, Field},
+    widgets::HexDump,
+};
 use kawaiifi::{Ie, InformationElement};
-use std::{fmt::Display, ops::Deref};
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    ops::{Deref, Range},
+};
 use tui::{
     buffer::Buffer,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    text::Spans,
     widgets::{
         Block, Borders, List, ListItem, ListState, Row, StatefulWidget, Table, TableState, Widget,
     },
 };
+use unicode_width::UnicodeWidthChar;
 
 pub struct IeTable {
     layout: Layout,
+    detail_layout: Layout,
 }
 
 #[derive(Debug)]
 pub struct IeTableState {
+    // The BSS the IEs came from
+    bssid: Option<String>,
     ies: Vec<Ie>,
     rows: Vec<IeTableRow>,
     column_headers: Vec<IeTableColumnHeader>,
     table_state: TableState,
     list_state: ListState,
+    // Paths of child indices from the top level fields down to each expanded field
+    expanded_fields: HashSet<Vec<usize>>,
     is_focused: bool,
+    field_list_is_focused: bool,
 }
 
 #[derive(Debug)]
@@ -28,6 +44,15 @@ struct IeTableRow {
     values: Vec<String>,
 }
 
+// A field as it appears in the flattened, partially expanded field tree
+#[derive(Debug)]
+struct FieldRow {
+    path: Vec<usize>,
+    text: String,
+    range: Option<Range<usize>>,
+    has_children: bool,
+}
+
 #[derive(Debug)]
 pub enum IeTableColumnHeader {
     Id,
@@ -42,6 +67,9 @@ impl IeTable {
                 .direction(Direction::Horizontal)
                 .margin(1)
                 .constraints([Constraint::Percentage(33), Constraint::Percentage(67)]),
+            detail_layout: Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(60), Constraint::Percentage(40)]),
         }
     }
 }
@@ -83,23 +111,82 @@ impl StatefulWidget for IeTable {
             &mut state.table_state,
         );
 
-        // Only render the list of IE fields if an IE is selected in the table
-        if let Some(ie) = state.selected_ie() {
-            let rows = ie
-                .information_fields()
+        // Only render the list of IE fields and the hex dump if an IE is selected in the table
+        let selected_ie = state
+            .selected_ie()
+            .map(|ie| (decode::ie_name(ie), ie.bytes().to_vec()));
+        if let Some((title, bytes)) = selected_ie {
+            let detail_areas = self.detail_layout.split(areas[1]);
+            let field_rows = state.visible_fields();
+            let width = detail_areas[0].width.saturating_sub(2) as usize;
+            let lines = field_rows
                 .iter()
-                .map(|field| field.to_string())
-                .collect::<Vec<String>>();
-            let items = rows
+                .map(|row| {
+                    let indent = "  ".repeat(row.path.len() - 1);
+                    let marker = match (row.has_children, state.expanded_fields.contains(&row.path))
+                    {
+                        (true, true) => "▾ ",
+                        (true, false) => "▸ ",
+                        (false, _) => "  ",
+                    };
+                    // Long fields wrap onto continuation lines indented under the marker
+                    wrap(&row.text, width.saturating_sub(indent.len() + 2))
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, line)| {
+                            if i == 0 {
+                                format!("{}{}{}", indent, marker, line)
+                            } else {
+                                format!("{}  {}", indent, line)
+                            }
+                        })
+                        .collect::<Vec<String>>()
+                })
+                .collect::<Vec<Vec<String>>>();
+            let items = lines
                 .iter()
-                .map(|row| ListItem::new(row.as_str()))
+                .map(|row_lines| {
+                    ListItem::new(
+                        row_lines
+                            .iter()
+                            .map(|line| Spans::from(line.as_str()))
+                            .collect::<Vec<Spans>>(),
+                    )
+                })
                 .collect::<Vec<ListItem>>();
 
-            Widget::render(
-                List::new(items).block(Block::default().title(ie.name()).borders(Borders::ALL)),
-                areas[1],
+            StatefulWidget::render(
+                List::new(items)
+                    .block(
+                        Block::default()
+                            .title(title.as_str())
+                            .borders(Borders::ALL)
+                            .border_style(if state.field_list_is_focused {
+                                Style::default().fg(Color::Yellow)
+                            } else {
+                                Style::default()
+                            }),
+                    )
+                    .highlight_style(
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                detail_areas[0],
                 buf,
+                &mut state.list_state,
             );
+
+            // Highlight the bytes that the selected field was decoded from
+            let highlight = state
+                .list_state
+                .selected()
+                .and_then(|selected| field_rows.get(selected))
+                .and_then(|row| row.range.clone());
+            HexDump::new(&bytes)
+                .highlight(highlight)
+                .block(Block::default().title("Bytes").borders(Borders::ALL))
+                .render(detail_areas[1], buf);
         }
     }
 }
@@ -121,7 +208,28 @@ impl IeTableState {
         self.is_focused = false;
     }
 
-    pub fn set_ies(&mut self, ies: &[Ie]) {
+    pub fn is_field_list_focused(&self) -> bool {
+        self.field_list_is_focused
+    }
+
+    pub fn focus_field_list(&mut self) {
+        self.field_list_is_focused = true;
+        if self.list_state.selected().is_none() {
+            self.select_next_field();
+        }
+    }
+
+    pub fn unfocus_field_list(&mut self) {
+        self.field_list_is_focused = false;
+    }
+
+    pub fn set_ies(&mut self, bssid: Option<String>, ies: &[Ie]) {
+        // Expanded fields are tracked by position, so they don't carry over to another BSS
+        if self.bssid != bssid {
+            self.bssid = bssid;
+            self.reset_fields();
+        }
+
         let previously_selected_ie = {
             if let Some(selected) = self.table_state.selected() {
                 self.ies.get(selected)
@@ -133,8 +241,12 @@ impl IeTableState {
         // Find the position of the previously selected IE in the new IEs
         // Save that position to the table state
         if let Some(previously_selected_ie) = previously_selected_ie {
-            self.table_state
-                .select(ies.iter().position(|ie| ie == previously_selected_ie));
+            let position = ies.iter().position(|ie| ie == previously_selected_ie);
+            // The field selection only makes sense for the IE it was made in
+            if position.is_none() {
+                self.reset_fields();
+            }
+            self.table_state.select(position);
         }
 
         // Make sure that an IE is always selected
@@ -153,6 +265,7 @@ impl IeTableState {
         if let Some(selected) = self.table_state.selected() {
             if selected < self.ies.len() - 1 {
                 self.table_state.select(Some(selected + 1));
+                self.reset_fields();
             }
         } else if !self.ies.is_empty() {
             self.table_state.select(Some(0));
@@ -163,10 +276,61 @@ impl IeTableState {
         if let Some(selected) = self.table_state.selected() {
             if selected > 0 && !self.ies.is_empty() {
                 self.table_state.select(Some(selected - 1));
+                self.reset_fields();
+            }
+        }
+    }
+
+    pub fn select_next_field(&mut self) {
+        let field_count = self.visible_fields().len();
+        match self.list_state.selected() {
+            Some(selected) if selected + 1 < field_count => {
+                self.list_state.select(Some(selected + 1))
+            }
+            None if field_count > 0 => self.list_state.select(Some(0)),
+            _ => (),
+        }
+    }
+
+    pub fn select_previous_field(&mut self) {
+        if let Some(selected) = self.list_state.selected() {
+            if selected > 0 {
+                self.list_state.select(Some(selected - 1));
+            }
+        }
+    }
+
+    // Expands or collapses the selected field if it has nested fields
+    pub fn toggle_selected_field(&mut self) {
+        let selected_row = self
+            .list_state
+            .selected()
+            .and_then(|selected| self.visible_fields().into_iter().nth(selected));
+        if let Some(row) = selected_row {
+            if row.has_children && !self.expanded_fields.remove(&row.path) {
+                self.expanded_fields.insert(row.path);
             }
         }
     }
 
+    fn reset_fields(&mut self) {
+        self.list_state.select(None);
+        self.expanded_fields.clear();
+    }
+
+    fn visible_fields(&self) -> Vec<FieldRow> {
+        let mut rows = Vec::new();
+        if let Some(ie) = self.selected_ie() {
+            flatten_fields(
+                &decode::ie_fields(ie, &self.ies),
+                &self.expanded_fields,
+                &mut Vec::new(),
+                &mut rows,
+            );
+        }
+        rows
+    }
+
     pub fn selected_ie(&self) -> Option<&Ie> {
         if let Some(selected) = self.table_state.selected() {
             self.ies.get(selected)
@@ -184,11 +348,14 @@ impl Default for IeTableState {
                 IeTableColumnHeader::Element,
                 IeTableColumnHeader::Length,
             ],
+            bssid: None,
             ies: Vec::default(),
             rows: Vec::default(),
             table_state: TableState::default(),
             list_state: ListState::default(),
+            expanded_fields: HashSet::new(),
             is_focused: false,
+            field_list_is_focused: false,
         }
     }
 }
@@ -200,7 +367,7 @@ impl IeTableRow {
                 .iter()
                 .map(|column_header| match column_header {
                     IeTableColumnHeader::Id => ie.id().to_string(),
-                    IeTableColumnHeader::Element => ie.name().to_string(),
+                    IeTableColumnHeader::Element => decode::ie_name(ie),
                     IeTableColumnHeader::Length => format!("{} B", ie.bytes().len()),
                 })
                 .collect(),
@@ -216,6 +383,49 @@ impl Deref for IeTableRow {
     }
 }
 
+fn flatten_fields(
+    fields: &[Field],
+    expanded_fields: &HashSet<Vec<usize>>,
+    path: &mut Vec<usize>,
+    rows: &mut Vec<FieldRow>,
+) {
+    for (i, field) in fields.iter().enumerate() {
+        path.push(i);
+        rows.push(FieldRow {
+            path: path.clone(),
+            text: field.to_string(),
+            range: field.range.clone(),
+            has_children: !field.children.is_empty(),
+        });
+        if expanded_fields.contains(path) {
+            flatten_fields(&field.children, expanded_fields, path, rows);
+        }
+        path.pop();
+    }
+}
+
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    if width == 0 {
+        return vec![text.to_string()];
+    }
+
+    // Break by terminal columns rather than characters so that wide characters don't overflow
+    let mut lines = vec![String::new()];
+    let mut line_width = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or_default();
+        if line_width + char_width > width && line_width > 0 {
+            lines.push(String::new());
+            line_width = 0;
+        }
+        if let Some(line) = lines.last_mut() {
+            line.push(c);
+        }
+        line_width += char_width;
+    }
+    lines
+}
+
 impl Display for IeTableColumnHeader {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
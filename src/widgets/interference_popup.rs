@@ -0,0 +1,61 @@
+use crate::analysis::Interference;
+use tui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Row, Table, Widget},
+};
+
+const WORST_OFFENDER_COUNT: usize = 10;
+
+pub struct InterferencePopup<'a> {
+    bssid: &'a str,
+    interference: &'a Interference,
+}
+
+impl<'a> InterferencePopup<'a> {
+    pub fn new(bssid: &'a str, interference: &'a Interference) -> InterferencePopup<'a> {
+        InterferencePopup {
+            bssid,
+            interference,
+        }
+    }
+}
+
+impl<'a> Widget for InterferencePopup<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let rows = self
+            .interference
+            .worst(WORST_OFFENDER_COUNT)
+            .iter()
+            .map(|interferer| {
+                vec![
+                    interferer.bssid.clone(),
+                    interferer.ssid.clone(),
+                    interferer.channel.clone(),
+                    format!("{} dBm", interferer.signal_dbm),
+                    format!("{:.0}%", interferer.overlap * 100.0),
+                ]
+            })
+            .collect::<Vec<Vec<String>>>();
+
+        let title = format!("Interference on {}: {}", self.bssid, self.interference);
+
+        Clear.render(area, buf);
+        Table::new(
+            ["BSSID", "SSID", "Channel", "Signal", "Overlap"].iter(),
+            rows.iter().map(|row| Row::Data(row.iter())),
+        )
+        .block(Block::default().title(title.as_str()).borders(Borders::ALL))
+        .header_style(Style::default().add_modifier(Modifier::BOLD))
+        .widths(&[
+            Constraint::Percentage(25),
+            Constraint::Percentage(30),
+            Constraint::Percentage(15),
+            Constraint::Percentage(15),
+            Constraint::Percentage(15),
+        ])
+        .column_spacing(1)
+        .render(area, buf);
+    }
+}
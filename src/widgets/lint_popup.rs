@@ -0,0 +1,45 @@
+use crate::analysis::LintProblem;
+use tui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Row, Table, Widget},
+};
+
+pub struct LintPopup<'a> {
+    bssid: &'a str,
+    problems: &'a [LintProblem],
+}
+
+impl<'a> LintPopup<'a> {
+    pub fn new(bssid: &'a str, problems: &'a [LintProblem]) -> LintPopup<'a> {
+        LintPopup { bssid, problems }
+    }
+}
+
+impl<'a> Widget for LintPopup<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let rows = self
+            .problems
+            .iter()
+            .map(|problem| vec![problem.element.clone(), problem.description.clone()])
+            .collect::<Vec<Vec<String>>>();
+
+        let title = if self.problems.is_empty() {
+            format!("Configuration problems of {}: none", self.bssid)
+        } else {
+            format!("Configuration problems of {}", self.bssid)
+        };
+
+        Clear.render(area, buf);
+        Table::new(
+            ["Element", "Problem"].iter(),
+            rows.iter().map(|row| Row::Data(row.iter())),
+        )
+        .block(Block::default().title(title.as_str()).borders(Borders::ALL))
+        .header_style(Style::default().add_modifier(Modifier::BOLD))
+        .widths(&[Constraint::Percentage(25), Constraint::Percentage(75)])
+        .column_spacing(1)
+        .render(area, buf);
+    }
+}
@@ -0,0 +1,221 @@
+use crate::decode;
+use kawaiifi::Bss;
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+use tui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Block, Borders, Gauge, Paragraph, Sparkline, StatefulWidget, Widget},
+};
+
+const MAX_HISTORY: usize = 500;
+// The signal range the gauge and chart cover
+const MIN_SIGNAL_DBM: i32 = -100;
+const MAX_SIGNAL_DBM: i32 = -30;
+// The trend compares the average of the latest samples with the samples before them
+const TREND_SAMPLES: usize = 3;
+const TREND_THRESHOLD_DB: f64 = 2.0;
+const SLOWEST_BELL_MS: f64 = 2000.0;
+const FASTEST_BELL_MS: f64 = 100.0;
+
+pub struct LocateView;
+
+#[derive(Debug)]
+pub struct LocateState {
+    bssid: String,
+    ssid: String,
+    channel: u8,
+    // None while the BSS is missing from the latest scan
+    signal_dbm: Option<i32>,
+    peak_dbm: Option<i32>,
+    history: VecDeque<i32>,
+    bell: bool,
+    last_bell: Instant,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Trend {
+    Warmer,
+    Colder,
+    Steady,
+}
+
+impl LocateView {
+    pub fn new() -> LocateView {
+        LocateView {}
+    }
+}
+
+impl StatefulWidget for LocateView {
+    type State = LocateState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let title = format!(
+            "Locating {} \"{}\" on channel {} (Esc: back, b: bell, p: reset peak)",
+            state.bssid, state.ssid, state.channel
+        );
+        let block = Block::default()
+            .title(title.as_str())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Percentage(40),
+                    Constraint::Length(3),
+                    Constraint::Min(0),
+                ]
+                .as_ref(),
+            )
+            .split(inner);
+
+        let (ratio, label) = match state.signal_dbm {
+            Some(signal_dbm) => (signal_ratio(signal_dbm), format!("{} dBm", signal_dbm)),
+            None => (0.0, "Not seen in the latest scan".to_string()),
+        };
+        Gauge::default()
+            .block(Block::default().title("Signal").borders(Borders::ALL))
+            .style(Style::default().fg(Color::Green))
+            .ratio(ratio)
+            .label(label.as_str())
+            .render(chunks[0], buf);
+
+        let (trend, trend_color) = match state.trend() {
+            Trend::Warmer => ("↑ Getting warmer", Color::Red),
+            Trend::Colder => ("↓ Getting colder", Color::Blue),
+            Trend::Steady => ("→ Steady", Color::Reset),
+        };
+        Paragraph::new(format!(
+            "{}    Peak {}    Bell {}",
+            trend,
+            state
+                .peak_dbm
+                .map(|peak_dbm| format!("{} dBm", peak_dbm))
+                .unwrap_or_else(|| "-".to_string()),
+            if state.bell { "on" } else { "off" }
+        ))
+        .style(Style::default().fg(trend_color))
+        .block(Block::default().borders(Borders::ALL))
+        .render(chunks[1], buf);
+
+        // The sparkline draws from the start of its data, so only pass the samples that fit
+        let width = chunks[2].width.saturating_sub(2) as usize;
+        let data = state
+            .history
+            .iter()
+            .skip(state.history.len().saturating_sub(width))
+            .map(|signal_dbm| (signal_dbm - MIN_SIGNAL_DBM).max(0) as u64)
+            .collect::<Vec<u64>>();
+        Sparkline::default()
+            .block(
+                Block::default()
+                    .title("Signal history")
+                    .borders(Borders::ALL),
+            )
+            .data(&data)
+            .max((MAX_SIGNAL_DBM - MIN_SIGNAL_DBM) as u64)
+            .style(Style::default().fg(Color::Green))
+            .render(chunks[2], buf);
+    }
+}
+
+impl LocateState {
+    pub fn new(bss: &Bss) -> Self {
+        let mut state = LocateState {
+            bssid: bss.bssid().to_string(),
+            ssid: decode::display_ssid(bss.ies()).unwrap_or_default(),
+            channel: bss.channel().number(),
+            signal_dbm: None,
+            peak_dbm: None,
+            history: VecDeque::new(),
+            bell: false,
+            last_bell: Instant::now(),
+        };
+        state.update(Some(bss));
+        state
+    }
+
+    pub fn bssid(&self) -> &str {
+        &self.bssid
+    }
+
+    pub fn update(&mut self, bss: Option<&Bss>) {
+        self.signal_dbm = bss.map(|bss| i32::from(bss.signal_dbm()));
+
+        if let Some(bss) = bss {
+            let signal_dbm = i32::from(bss.signal_dbm());
+            self.channel = bss.channel().number();
+            self.peak_dbm = Some(
+                self.peak_dbm
+                    .map_or(signal_dbm, |peak| peak.max(signal_dbm)),
+            );
+            self.history.push_back(signal_dbm);
+            if self.history.len() > MAX_HISTORY {
+                self.history.pop_front();
+            }
+        }
+    }
+
+    pub fn reset_peak(&mut self) {
+        self.peak_dbm = self.signal_dbm;
+    }
+
+    pub fn toggle_bell(&mut self) {
+        self.bell = !self.bell;
+    }
+
+    // Whether to ring the bell now, which rings faster the stronger the signal is
+    pub fn bell_due(&mut self) -> bool {
+        let signal_dbm = match self.signal_dbm {
+            Some(signal_dbm) if self.bell => signal_dbm,
+            _ => return false,
+        };
+
+        let interval_ms =
+            SLOWEST_BELL_MS - signal_ratio(signal_dbm) * (SLOWEST_BELL_MS - FASTEST_BELL_MS);
+        if self.last_bell.elapsed() >= Duration::from_millis(interval_ms as u64) {
+            self.last_bell = Instant::now();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn trend(&self) -> Trend {
+        if self.history.len() < TREND_SAMPLES * 2 {
+            return Trend::Steady;
+        }
+
+        let average = |skip: usize| {
+            self.history
+                .iter()
+                .rev()
+                .skip(skip)
+                .take(TREND_SAMPLES)
+                .sum::<i32>() as f64
+                / TREND_SAMPLES as f64
+        };
+        let latest = average(0);
+        let previous = average(TREND_SAMPLES);
+
+        if latest - previous >= TREND_THRESHOLD_DB {
+            Trend::Warmer
+        } else if previous - latest >= TREND_THRESHOLD_DB {
+            Trend::Colder
+        } else {
+            Trend::Steady
+        }
+    }
+}
+
+fn signal_ratio(signal_dbm: i32) -> f64 {
+    ((signal_dbm - MIN_SIGNAL_DBM) as f64 / (MAX_SIGNAL_DBM - MIN_SIGNAL_DBM) as f64)
+        .clamp(0.0, 1.0)
+}
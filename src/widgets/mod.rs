@@ -1,15 +1,80 @@
 mod bss_table;
+mod change_log;
+mod channel_summary;
+mod discovery_popup;
+mod edca_popup;
+mod ess_popup;
+mod findings_popup;
+mod hex_dump;
+mod ie_diff;
 mod ie_table;
+mod interference_popup;
+mod lint_popup;
+mod locate;
+mod regulatory_popup;
 
 pub use bss_table::BssTable;
 pub use bss_table::BssTableColumnHeader;
 pub use bss_table::BssTableState;
 
+pub use change_log::ChangeLogPopup;
+
+pub use channel_summary::ChannelSummaryPopup;
+
+pub use discovery_popup::DiscoveryPopup;
+
+pub use edca_popup::EdcaPopup;
+
+pub use ess_popup::EssPopup;
+
+pub use findings_popup::FindingsPopup;
+
+pub use hex_dump::HexDump;
+
+pub use ie_diff::IeDiffState;
+pub use ie_diff::IeDiffView;
+
 pub use ie_table::IeTable;
 pub use ie_table::IeTableState;
 
+pub use interference_popup::InterferencePopup;
+
+pub use lint_popup::LintPopup;
+
+pub use locate::{LocateState, LocateView};
+
+pub use regulatory_popup::RegulatoryPopup;
+
+use tui::layout::{Constraint, Direction, Layout, Rect};
+
 #[derive(Debug, Copy, Clone)]
 pub enum TableSortOrder {
     Ascending,
     Descending,
 }
+
+pub fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
+            ]
+            .as_ref(),
+        )
+        .split(vertical[1])[1]
+}
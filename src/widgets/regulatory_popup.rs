@@ -0,0 +1,164 @@
+use crate::{
+    analysis::{self, ScanAnalysis},
+    decode::{regulatory, Country, TpcReport},
+};
+use kawaiifi::Bss;
+use tui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Row, Table, Widget},
+};
+
+pub struct RegulatoryPopup<'a> {
+    bss: &'a Bss,
+    analysis: &'a ScanAnalysis,
+}
+
+impl<'a> RegulatoryPopup<'a> {
+    pub fn new(bss: &'a Bss, analysis: &'a ScanAnalysis) -> RegulatoryPopup<'a> {
+        RegulatoryPopup { bss, analysis }
+    }
+}
+
+impl<'a> Widget for RegulatoryPopup<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let ies = self.bss.ies();
+        let band = analysis::frequency_band(self.bss);
+        let channel = self.bss.channel().number();
+        let country = Country::from_ies(ies);
+        let power_constraint = regulatory::power_constraint_db(ies);
+
+        // Rows that need attention are shown in red
+        let mut rows = vec![(
+            false,
+            vec![
+                "Channel".to_string(),
+                if analysis::is_dfs(self.bss) {
+                    format!("{} ({}, DFS)", channel, band)
+                } else {
+                    format!("{} ({})", channel, band)
+                },
+            ],
+        )];
+
+        match &country {
+            Some(country) => {
+                let mismatch = self.analysis.is_country_mismatch(self.bss);
+                rows.push((
+                    mismatch,
+                    vec![
+                        "Country".to_string(),
+                        match self.analysis.majority_country() {
+                            Some(majority) if mismatch => {
+                                format!("{}, but most APs advertise {}", country.code, majority)
+                            }
+                            _ => country.code.clone(),
+                        },
+                    ],
+                ));
+                rows.push((
+                    false,
+                    vec!["Environment".to_string(), country.environment_name()],
+                ));
+                rows.extend(country.subbands.iter().map(|subband| {
+                    let channels = subband.channels(band);
+                    (
+                        false,
+                        vec![
+                            "Subband".to_string(),
+                            format!(
+                                "Channels {}-{}, max {} dBm{}",
+                                channels.start(),
+                                channels.end(),
+                                subband.max_tx_power_dbm,
+                                if channels.contains(&(channel as u16)) {
+                                    " (operating)"
+                                } else {
+                                    ""
+                                }
+                            ),
+                        ],
+                    )
+                }));
+                rows.extend(country.operating_classes.iter().map(|operating_class| {
+                    (
+                        false,
+                        vec![
+                            "Operating Class".to_string(),
+                            format!(
+                                "{}, coverage class {}",
+                                operating_class.operating_class, operating_class.coverage_class
+                            ),
+                        ],
+                    )
+                }));
+                if country.subband(channel, band).is_none() && !country.subbands.is_empty() {
+                    rows.push((
+                        true,
+                        vec![
+                            "Operating Channel".to_string(),
+                            format!("Not listed for {}", country.code),
+                        ],
+                    ));
+                }
+            }
+            None => rows.push((
+                false,
+                vec!["Country".to_string(), "Not advertised".to_string()],
+            )),
+        }
+
+        if let Some(power_constraint) = power_constraint {
+            let local_max = country
+                .as_ref()
+                .and_then(|country| country.subband(channel, band))
+                .map(|subband| {
+                    format!(
+                        ", local max {} dBm",
+                        subband.max_tx_power_dbm as i16 - power_constraint as i16
+                    )
+                })
+                .unwrap_or_default();
+            rows.push((
+                false,
+                vec![
+                    "Power Constraint".to_string(),
+                    format!("{} dB{}", power_constraint, local_max),
+                ],
+            ));
+        }
+
+        if let Some(tpc_report) = TpcReport::from_ies(ies) {
+            rows.push((
+                false,
+                vec![
+                    "TPC Report".to_string(),
+                    format!(
+                        "Transmit power {} dBm, link margin {} dB",
+                        tpc_report.tx_power_dbm, tpc_report.link_margin_db
+                    ),
+                ],
+            ));
+        }
+
+        let title = format!("Regulatory information of {}", self.bss.bssid());
+
+        Clear.render(area, buf);
+        Table::new(
+            ["Field", "Value"].iter(),
+            rows.iter().map(|(attention, row)| {
+                if *attention {
+                    Row::StyledData(row.iter(), Style::default().fg(Color::Red))
+                } else {
+                    Row::Data(row.iter())
+                }
+            }),
+        )
+        .block(Block::default().title(title.as_str()).borders(Borders::ALL))
+        .header_style(Style::default().add_modifier(Modifier::BOLD))
+        .widths(&[Constraint::Percentage(25), Constraint::Percentage(75)])
+        .column_spacing(1)
+        .render(area, buf);
+    }
+}